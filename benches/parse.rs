@@ -0,0 +1,44 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+const SHORT: &str = "これはテストです。";
+const MEDIUM: &str = "日本語の文章をいい感じに分割します。";
+const LONG: &str =
+    "日本語の文章において語の区切りに空白を挟んで記述することを分かち書きと言います。\
+    BudouXは機械学習モデルを用いて、読みやすい位置で文章を分割します。\
+    あなたに寄り添う最先端のテクノロジー。今日はとても天気です。";
+const ASCII: &str =
+    "The quick brown fox jumps over the lazy dog. Pack my box with five dozen liquor jugs.";
+const ZH_HANS: &str = "我们的使命是整合全球信息，供大众使用，让人人受益。今天是晴天。";
+
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    let ja = budoux::models::default_japanese_model();
+    let zh_hans = budoux::models::default_simplified_chinese_model();
+    for (name, model, input) in [
+        ("ja/short", ja, SHORT),
+        ("ja/medium", ja, MEDIUM),
+        ("ja/long", ja, LONG),
+        ("ja/ascii", ja, ASCII),
+        ("zh_hans", zh_hans, ZH_HANS),
+    ] {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_function(name, |b| b.iter(|| model.parse(black_box(input))));
+    }
+    group.finish();
+}
+
+fn bench_load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("from_str");
+    for (name, json) in [
+        ("ja", include_str!("../resources/ja.json")),
+        ("zh_hans", include_str!("../resources/zh-hans.json")),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| budoux::Model::from_str(black_box(json)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse, bench_load);
+criterion_main!(benches);
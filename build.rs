@@ -0,0 +1,36 @@
+//! build converts the bundled model json into Rust literals, so that no json is parsed at runtime.
+
+use std::{collections::BTreeMap, env, fmt::Write, fs, path::Path};
+
+/// MODELS is the bundled model json files in resources and the names of the generated files.
+const MODELS: [(&str, &str); 5] = [
+    ("ja.json", "ja.rs"),
+    ("latin.json", "latin.rs"),
+    ("th.json", "th.rs"),
+    ("zh-hans.json", "zh_hans.rs"),
+    ("zh-hant.json", "zh_hant.rs"),
+];
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    for (json, out) in MODELS {
+        let path = Path::new("resources").join(json);
+        println!("cargo:rerun-if-changed={}", path.display());
+
+        let src = fs::read_to_string(&path).unwrap();
+        let model: BTreeMap<String, BTreeMap<String, i32>> =
+            serde_json::from_str(&src).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+
+        let mut code = String::from("&[\n");
+        for (group, features) in &model {
+            write!(code, "    ({:?}, &[", group).unwrap();
+            for (key, score) in features {
+                write!(code, "({:?}, {}), ", key, score).unwrap();
+            }
+            code.push_str("]),\n");
+        }
+        code.push_str("]\n");
+        fs::write(Path::new(&out_dir).join(out), code).unwrap();
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -50,11 +50,11 @@ impl Component for Model {
                     <textarea
                         placeholder={ "日本語の文章を入力してください。" }
                         value={ self.input.clone() }
-                        oninput={ ctx.link().callback(|e| Msg::Input(e)) }
+                        oninput={ ctx.link().callback(Msg::Input) }
                     >
                     </textarea>
                     {
-                        if self.input.len() != 0 && words.len() > 0 {
+                        if !self.input.is_empty() && !words.is_empty() {
                             html! {
                                 <ol>
                                     { for words.iter().map(|text| { html! { <li>{ text }</li> } } ) }
@@ -0,0 +1,23 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let lossy = String::from_utf8_lossy(data);
+    let inputs = match std::str::from_utf8(data) {
+        Ok(input) => vec![input, &lossy],
+        Err(_) => vec![&*lossy],
+    };
+    for model in [
+        budoux::models::default_japanese_model(),
+        budoux::models::default_simplified_chinese_model(),
+        budoux::models::default_traditional_chinese_model(),
+        budoux::models::default_thai_model(),
+    ] {
+        for &input in &inputs {
+            let words = model.parse(input);
+            assert_eq!(words.concat(), input);
+            assert!(words.iter().all(|w| !w.is_empty()));
+        }
+    }
+});
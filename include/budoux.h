@@ -0,0 +1,31 @@
+/* C bindings of BudouX-rs. Enabled by the `ffi` feature. */
+#ifndef BUDOUX_H
+#define BUDOUX_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct BudouxModel BudouxModel;
+
+/* Static models. Must not be freed. */
+const BudouxModel *budoux_default_japanese(void);
+const BudouxModel *budoux_default_thai(void);
+const BudouxModel *budoux_default_simplified_chinese(void);
+const BudouxModel *budoux_default_traditional_chinese(void);
+
+/* Returns NULL on error. Free with budoux_model_free. */
+BudouxModel *budoux_model_from_json(const char *json);
+void budoux_model_free(BudouxModel *model);
+
+/* Returns NULL on error. Free with budoux_free_result and the same count. */
+char **budoux_parse(const BudouxModel *model, const char *input, size_t *out_count);
+void budoux_free_result(char **result, size_t count);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* BUDOUX_H */
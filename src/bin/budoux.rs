@@ -0,0 +1,167 @@
+//! budoux splits sentences read from stdin and writes the chunks separated by a delimiter.
+
+use std::{
+    borrow::Cow,
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use budoux::{Model, ParseOptions, DEFAULT_THRESHOLD, DEFAULT_VISUALIZE_SEPARATOR};
+use clap::Parser;
+
+/// Args is command line arguments.
+#[derive(Debug, Parser)]
+#[command(
+    version,
+    about = "Split sentences read from stdin into chunks with BudouX."
+)]
+struct Args {
+    /// BCP 47 language tag of the bundled model, e.g. ja, zh-Hans, zh-Hant or th.
+    #[arg(short, long, default_value = "ja")]
+    lang: String,
+    /// Path of a BudouX model json used instead of the bundled model.
+    #[arg(short, long, conflicts_with = "lang")]
+    model: Option<PathBuf>,
+    /// Score a boundary must reach to insert a break.
+    #[arg(short, long, default_value_t = DEFAULT_THRESHOLD, allow_negative_numbers = true)]
+    threshold: i32,
+    /// Delimiter written between chunks.
+    #[arg(short, long, default_value = DEFAULT_VISUALIZE_SEPARATOR)]
+    delimiter: String,
+    /// Treat input as html and insert <wbr> at boundaries instead of the delimiter.
+    #[cfg(feature = "html")]
+    #[arg(long, conflicts_with_all = ["threshold", "delimiter"])]
+    html: bool,
+}
+
+/// load returns the model selected by the arguments. Bundled models are borrowed.
+fn load(args: &Args) -> Result<Cow<'static, Model>, String> {
+    match &args.model {
+        Some(path) => {
+            let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            Model::from_reader(BufReader::new(file))
+                .map(Cow::Owned)
+                .map_err(|e| format!("{}: {}", path.display(), e))
+        }
+        None => Model::for_language(&args.lang)
+            .map(Cow::Borrowed)
+            .ok_or_else(|| format!("unsupported language: {}", args.lang)),
+    }
+}
+
+/// run splits each line of input and writes the result to output.
+fn run(args: &Args, model: &Model, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let options = ParseOptions {
+        threshold: args.threshold,
+        ..Default::default()
+    };
+    let mut parser = model.parser();
+    for line in input.lines() {
+        let line = line?;
+        #[cfg(feature = "html")]
+        if args.html {
+            writeln!(output, "{}", model.translate_html(&line))?;
+            continue;
+        }
+        let words = parser.parse_with_options(&line, &options);
+        writeln!(output, "{}", words.join(&args.delimiter))?;
+    }
+    output.flush()
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let model = match load(&args) {
+        Ok(model) => model,
+        Err(e) => {
+            eprintln!("budoux: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    match run(&args, &model, io::stdin().lock(), io::stdout().lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        // a closed pipe, e.g. `budoux | head`, is not an error.
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("budoux: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use clap::Parser;
+
+    use super::{load, run, Args};
+
+    fn split(args: &[&str], input: &str) -> String {
+        let args = Args::try_parse_from(["budoux"].iter().chain(args)).unwrap();
+        let model = load(&args).unwrap();
+        let mut out = vec![];
+        run(&args, &model, input.as_bytes(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_run() {
+        assert_eq!(split(&[], ""), "");
+        assert_eq!(
+            split(&[], "これはテストです。\n今日は晴天です。\n"),
+            "これは|テストです。\n今日は|晴天です。\n"
+        );
+        assert_eq!(
+            split(&["-d", " / "], "これはテストです。"),
+            "これは / テストです。\n"
+        );
+        assert_eq!(
+            split(&["--lang", "zh-Hans"], "我们的使命是整合全球信息"),
+            format!(
+                "{}\n",
+                budoux::models::default_simplified_chinese_model()
+                    .parse("我们的使命是整合全球信息")
+                    .join("|")
+            )
+        );
+        assert_eq!(split(&["--threshold", "-100000"], "水と油"), "水|と|油\n");
+    }
+
+    #[test]
+    fn test_load() {
+        let args = Args::try_parse_from(["budoux", "--lang", "en"]).unwrap();
+        assert!(load(&args).is_err());
+
+        let args = Args::try_parse_from(["budoux", "--model", "resources/ja.json"]).unwrap();
+        assert_eq!(
+            &*load(&args).unwrap(),
+            budoux::models::default_japanese_model()
+        );
+
+        // bundled models are not copied.
+        let args = Args::try_parse_from(["budoux", "--lang", "ja"]).unwrap();
+        assert!(matches!(
+            load(&args).unwrap(),
+            Cow::Borrowed(m) if std::ptr::eq(m, budoux::models::default_japanese_model())
+        ));
+
+        let args = Args::try_parse_from(["budoux", "--model", "missing.json"]).unwrap();
+        assert!(load(&args).is_err());
+    }
+
+    #[cfg(feature = "html")]
+    #[test]
+    fn test_run_html() {
+        assert_eq!(
+            split(&["--html"], "<p>これはテストです。</p>"),
+            "<p>これは<wbr>テストです。</p>\n"
+        );
+        for conflict in [["--threshold", "0"], ["--delimiter", "/"]] {
+            let args = ["budoux", "--html"].into_iter().chain(conflict);
+            assert!(Args::try_parse_from(args).is_err());
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use alloc::string::String;
+
+use crate::{HashMap, Model};
+
+/// ModelBuilder is builder of `Model` which adds or merges feature scores.
+///
+/// # Examples
+///
+/// Overlay domain-specific features on the internal model.
+///
+/// ```rust
+/// let base = budoux::models::default_japanese_model();
+/// let model = budoux::ModelBuilder::from_model(base)
+///     .add_feature("UW4", "油", 10000)
+///     .build();
+///
+/// assert_eq!(model.parse("水と油"), vec!["水と", "油"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelBuilder {
+    map: HashMap<String, HashMap<String, i32>>,
+}
+
+impl ModelBuilder {
+    /// Creates a new `ModelBuilder` instance without features.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new `ModelBuilder` instance from the features of the model.
+    pub fn from_model(base: &Model) -> Self {
+        Self {
+            map: base.as_inner().clone(),
+        }
+    }
+
+    /// add_feature adds score to the feature, inserting the feature if it does not exist.
+    /// The sum saturates to the range of `i32`.
+    ///
+    /// * `group` - feature group, e.g. `UW1`.
+    /// * `key` - feature key.
+    /// * `score` - score to add.
+    pub fn add_feature(mut self, group: &str, key: &str, score: i32) -> Self {
+        let feature = self
+            .map
+            .entry(String::from(group))
+            .or_default()
+            .entry(String::from(key))
+            .or_default();
+        *feature = feature.saturating_add(score);
+        self
+    }
+
+    /// merge adds every feature score of the other model.
+    ///
+    /// Scores of overlapping features are summed, and other features are inserted.
+    pub fn merge(mut self, other: &Model) -> Self {
+        for (group, features) in other.as_inner() {
+            let group = self.map.entry(group.clone()).or_default();
+            for (key, score) in features {
+                let feature = group.entry(key.clone()).or_default();
+                *feature = feature.saturating_add(*score);
+            }
+        }
+        self
+    }
+
+    /// build returns the model and calculates the base score.
+    pub fn build(self) -> Model {
+        Model::new(self.map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ModelBuilder;
+    use crate::Model;
+
+    #[test]
+    fn test_build() {
+        assert_eq!(ModelBuilder::new().build(), Model::from_str("{}").unwrap());
+
+        let base = crate::models::default_japanese_model();
+        assert_eq!(&ModelBuilder::from_model(base).build(), base);
+    }
+
+    #[test]
+    fn test_add_feature() {
+        let m = ModelBuilder::new()
+            .add_feature("UW1", "あ", 1)
+            .add_feature("UW1", "あ", 2)
+            .add_feature("BW1", "いう", -4)
+            .build();
+
+        assert_eq!(
+            m,
+            Model::from_str(r#"{"UW1": {"あ": 3}, "BW1": {"いう": -4}}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let a = Model::from_str(r#"{"UW1": {"あ": 1, "い": 2}}"#).unwrap();
+        let b = Model::from_str(r#"{"UW1": {"あ": 10}, "UW2": {"う": 3}}"#).unwrap();
+
+        assert_eq!(
+            ModelBuilder::from_model(&a).merge(&b).build(),
+            Model::from_str(r#"{"UW1": {"あ": 11, "い": 2}, "UW2": {"う": 3}}"#).unwrap()
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use std::{num::NonZeroUsize, sync::Mutex};
+
+use lru::LruCache;
+
+use crate::Model;
+
+/// CachingModel is wrapper of `Model` which memoizes parse results in an LRU cache keyed by input.
+///
+/// It is useful when the same strings are parsed repeatedly, e.g. labels rendered on every repaint.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::num::NonZeroUsize;
+///
+/// let model = budoux::models::default_japanese_model();
+/// let cache = budoux::CachingModel::new(model, NonZeroUsize::new(128).unwrap());
+///
+/// assert_eq!(cache.get_or_parse("これはテストです。"), vec!["これは", "テストです。"]);
+/// assert_eq!(cache.get_or_parse("これはテストです。"), vec!["これは", "テストです。"]);
+/// assert_eq!(cache.len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct CachingModel<'m> {
+    model: &'m Model,
+    cache: Mutex<LruCache<String, Vec<String>>>,
+}
+
+impl<'m> CachingModel<'m> {
+    /// Creates a new `CachingModel` instance which holds up to `capacity` parse results.
+    pub fn new(model: &'m Model, capacity: NonZeroUsize) -> Self {
+        Self {
+            model,
+            cache: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// model returns the model used for parsing.
+    pub fn model(&self) -> &'m Model {
+        self.model
+    }
+
+    /// get_or_parse returns the cached parse result of input, parsing and caching it on a miss.
+    ///
+    /// * `input` - input sentences.
+    pub fn get_or_parse(&self, input: &str) -> Vec<String> {
+        let mut cache = self.lock();
+        if let Some(words) = cache.get(input) {
+            return words.clone();
+        }
+        let words = self.model.parse_owned(input);
+        cache.put(String::from(input), words.clone());
+        words
+    }
+
+    /// len returns the number of cached parse results.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// is_empty returns whether no parse result is cached.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// clear removes every cached parse result.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruCache<String, Vec<String>>> {
+        // the cache is never left inconsistent, so a poisoned lock is still usable.
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::CachingModel;
+
+    #[test]
+    fn test_get_or_parse() {
+        let m = crate::models::default_japanese_model();
+        let cache = CachingModel::new(m, NonZeroUsize::new(2).unwrap());
+
+        assert!(cache.is_empty());
+        for input in ["", "水と油", "これはテストです。", "水と油", "日本語"] {
+            assert_eq!(cache.get_or_parse(input), m.parse_owned(input));
+        }
+        assert_eq!(cache.len(), 2);
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.model(), m);
+    }
+}
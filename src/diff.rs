@@ -0,0 +1,120 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::Model;
+
+/// FeatureDiff is difference of a feature between two models returned by `Model::diff`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FeatureDiff {
+    /// group is the feature group, e.g. `UW1`.
+    pub group: String,
+    /// key is the feature key.
+    pub key: String,
+    /// old is the score in the base model, or `None` if the feature was added.
+    pub old: Option<i32>,
+    /// new is the score in the other model, or `None` if the feature was removed.
+    pub new: Option<i32>,
+}
+
+impl Model {
+    /// diff returns the features added, removed or changed in other compared with the model,
+    /// sorted by group and key.
+    ///
+    /// * `other` - model compared with the model.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let base = budoux::models::default_japanese_model();
+    /// let model = budoux::ModelBuilder::from_model(base)
+    ///     .add_feature("UW4", "🍣", 1000)
+    ///     .build();
+    /// let diff = base.diff(&model);
+    ///
+    /// assert_eq!(diff.len(), 1);
+    /// assert_eq!(diff[0].key, "🍣");
+    /// assert_eq!((diff[0].old, diff[0].new), (None, Some(1000)));
+    /// ```
+    pub fn diff(&self, other: &Model) -> Vec<FeatureDiff> {
+        let score = |model: &Model, group: &str, key: &str| {
+            model
+                .as_inner()
+                .get(group)
+                .and_then(|v| v.get(key))
+                .copied()
+        };
+        fn keys(model: &Model) -> impl Iterator<Item = (&String, &String)> {
+            model
+                .as_inner()
+                .iter()
+                .flat_map(|(group, features)| features.keys().map(move |key| (group, key)))
+        }
+
+        let mut diffs = keys(self)
+            .chain(keys(other).filter(|&(group, key)| score(self, group, key).is_none()))
+            .filter_map(|(group, key)| {
+                let old = score(self, group, key);
+                let new = score(other, group, key);
+                (old != new).then(|| FeatureDiff {
+                    group: group.clone(),
+                    key: key.clone(),
+                    old,
+                    new,
+                })
+            })
+            .collect::<Vec<_>>();
+        diffs.sort();
+        diffs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FeatureDiff;
+    use crate::Model;
+    use alloc::{string::String, vec};
+
+    fn diff(group: &str, key: &str, old: Option<i32>, new: Option<i32>) -> FeatureDiff {
+        FeatureDiff {
+            group: String::from(group),
+            key: String::from(key),
+            old,
+            new,
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let a = Model::from_str(r#"{"UW1": {"a": 1, "b": 2}, "UW2": {"c": 3}}"#).unwrap();
+        let b = Model::from_str(r#"{"UW1": {"a": 1, "b": 5}, "UW3": {"d": 4}}"#).unwrap();
+
+        assert_eq!(a.diff(&a), vec![]);
+        assert_eq!(
+            a.diff(&b),
+            vec![
+                diff("UW1", "b", Some(2), Some(5)),
+                diff("UW2", "c", Some(3), None),
+                diff("UW3", "d", None, Some(4)),
+            ]
+        );
+        assert_eq!(
+            b.diff(&a),
+            vec![
+                diff("UW1", "b", Some(5), Some(2)),
+                diff("UW2", "c", None, Some(3)),
+                diff("UW3", "d", Some(4), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_pruned() {
+        let m = crate::models::default_japanese_model();
+        let pruned = m.pruned(100);
+        let diffs = m.diff(&pruned);
+
+        assert_eq!(diffs.len(), m.feature_count() - pruned.feature_count());
+        assert!(diffs
+            .iter()
+            .all(|d| d.new.is_none() && d.old.unwrap().abs() < 100));
+    }
+}
@@ -0,0 +1,118 @@
+use alloc::vec::Vec;
+
+use crate::{saturate, FeatureMask, Model, DEFAULT_THRESHOLD};
+
+/// BoundaryExplanation is breakdown of the score of a boundary returned by `Model::explain`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundaryExplanation {
+    /// offset is the byte offset of the boundary in input.
+    pub offset: usize,
+    /// char_index is the index of the character after the boundary.
+    pub char_index: usize,
+    /// score is the total score of the boundary.
+    pub score: i32,
+    /// is_break is whether the score reaches `DEFAULT_THRESHOLD`, i.e. `parse` breaks there.
+    pub is_break: bool,
+    /// base_score is the base score of the model, which every boundary starts from.
+    pub base_score: i32,
+    /// unigram is the sum of the scores of the unigram features (`UW1` to `UW6`).
+    pub unigram: i32,
+    /// bigram is the sum of the scores of the bigram features (`BW1` to `BW3`).
+    pub bigram: i32,
+    /// trigram is the sum of the scores of the trigram features (`TW1` to `TW4`).
+    pub trigram: i32,
+}
+
+impl Model {
+    /// explain returns the breakdown of the score of every boundary between two characters of input.
+    ///
+    /// The score of each boundary is `base_score + unigram + bigram + trigram`, unless a value is
+    /// saturated to the range of `i32`.
+    ///
+    /// * `input` - input sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let explanations = model.explain("水と油");
+    ///
+    /// assert_eq!(explanations.len(), 2);
+    /// assert!(!explanations[0].is_break);
+    /// assert!(explanations[1].is_break);
+    /// let e = explanations[1];
+    /// assert_eq!(e.score, e.base_score + e.unigram + e.bigram + e.trigram);
+    /// ```
+    pub fn explain(&self, input: &str) -> Vec<BoundaryExplanation> {
+        let chars = input.char_indices().collect::<Vec<_>>();
+        (1..chars.len())
+            .map(|i| {
+                let (mut unigram, mut bigram, mut trigram) = (0, 0, 0);
+                for (group, score) in self.feature_scores(input, &chars, i, FeatureMask::ALL) {
+                    match &group[..2] {
+                        "UW" => unigram += score,
+                        "BW" => bigram += score,
+                        _ => trigram += score,
+                    }
+                }
+                let score = saturate(self.base_score + unigram + bigram + trigram);
+                BoundaryExplanation {
+                    offset: chars[i].0,
+                    char_index: i,
+                    score,
+                    is_break: score >= DEFAULT_THRESHOLD,
+                    base_score: saturate(self.base_score),
+                    unigram: saturate(unigram),
+                    bigram: saturate(bigram),
+                    trigram: saturate(trigram),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn test_explain() {
+        let m = crate::models::default_japanese_model();
+
+        assert_eq!(m.explain(""), vec![]);
+        assert_eq!(m.explain("日"), vec![]);
+
+        let input = "これはテストです。今日は晴天です。";
+        let explanations = m.explain(input);
+        assert_eq!(
+            explanations.iter().map(|e| e.score).collect::<Vec<_>>(),
+            m.scores(input)
+        );
+        assert_eq!(
+            explanations
+                .iter()
+                .filter(|e| e.is_break)
+                .map(|e| e.offset)
+                .collect::<Vec<_>>(),
+            m.parse_boundaries(input)
+        );
+        for (i, e) in explanations.iter().enumerate() {
+            assert_eq!(e.char_index, i + 1);
+            assert_eq!(e.score, e.base_score + e.unigram + e.bigram + e.trigram);
+        }
+    }
+
+    #[test]
+    fn test_explain_groups() {
+        let m = crate::Model::from_str(
+            r#"{"UW4": {"b": 1}, "UW3": {"a": 2}, "BW2": {"ab": 4}, "TW3": {"abc": 8}}"#,
+        )
+        .unwrap();
+        let e = m.explain("abc")[0];
+
+        assert_eq!(e.base_score, -15);
+        assert_eq!((e.unigram, e.bigram, e.trigram), (6, 8, 16));
+        assert_eq!(e.score, 15);
+        assert!(e.is_break);
+    }
+}
@@ -0,0 +1,189 @@
+//! C bindings.
+//!
+//! Build a shared library with `cargo rustc --release --features ffi --crate-type cdylib`,
+//! and see `include/budoux.h` for the declarations.
+//!
+//! Ownership rules:
+//!
+//! * A model returned by `budoux_default_*` is static and must not be freed.
+//! * A model returned by `budoux_model_from_json` must be freed by `budoux_model_free`.
+//! * A result returned by `budoux_parse` must be freed by `budoux_free_result` with the same count.
+
+use alloc::{boxed::Box, ffi::CString};
+use core::{
+    ffi::{c_char, CStr},
+    ptr,
+};
+
+use crate::Model;
+
+/// budoux_default_japanese returns trained machine learning model for japanese.
+///
+/// The returned model is static and must not be freed.
+#[no_mangle]
+pub extern "C" fn budoux_default_japanese() -> *const Model {
+    crate::models::default_japanese_model()
+}
+
+/// budoux_default_thai returns trained machine learning model for thai.
+///
+/// The returned model is static and must not be freed.
+#[no_mangle]
+pub extern "C" fn budoux_default_thai() -> *const Model {
+    crate::models::default_thai_model()
+}
+
+/// budoux_default_simplified_chinese returns trained machine learning model for simplified chinese.
+///
+/// The returned model is static and must not be freed.
+#[no_mangle]
+pub extern "C" fn budoux_default_simplified_chinese() -> *const Model {
+    crate::models::default_simplified_chinese_model()
+}
+
+/// budoux_default_traditional_chinese returns trained machine learning model for traditional chinese.
+///
+/// The returned model is static and must not be freed.
+#[no_mangle]
+pub extern "C" fn budoux_default_traditional_chinese() -> *const Model {
+    crate::models::default_traditional_chinese_model()
+}
+
+/// budoux_model_from_json loads model from NUL-terminated BudouX model json.
+///
+/// Returns null if `json` is null, not valid UTF-8 or not a valid model.
+///
+/// # Safety
+///
+/// `json` must be null or a valid NUL-terminated string.
+/// The returned model must be freed by `budoux_model_free`.
+#[no_mangle]
+pub unsafe extern "C" fn budoux_model_from_json(json: *const c_char) -> *mut Model {
+    if json.is_null() {
+        return ptr::null_mut();
+    }
+    match CStr::from_ptr(json).to_str().map(Model::from_str) {
+        Ok(Ok(model)) => Box::into_raw(Box::new(model)),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// budoux_model_free frees model returned by `budoux_model_from_json`.
+///
+/// # Safety
+///
+/// `model` must be null or returned by `budoux_model_from_json`, and must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn budoux_model_free(model: *mut Model) {
+    if !model.is_null() {
+        drop(Box::from_raw(model));
+    }
+}
+
+/// budoux_parse returns splitted NUL-terminated strings from NUL-terminated input.
+///
+/// The number of strings is stored into `out_count`.
+/// Returns null if any argument is null or `input` is not valid UTF-8.
+///
+/// # Safety
+///
+/// `model` must be null or a valid model, `input` must be null or a valid NUL-terminated string,
+/// and `out_count` must be null or valid for writes.
+/// The returned result must be freed by `budoux_free_result`.
+#[no_mangle]
+pub unsafe extern "C" fn budoux_parse(
+    model: *const Model,
+    input: *const c_char,
+    out_count: *mut usize,
+) -> *mut *mut c_char {
+    if model.is_null() || input.is_null() || out_count.is_null() {
+        return ptr::null_mut();
+    }
+    let input = match CStr::from_ptr(input).to_str() {
+        Ok(input) => input,
+        Err(_) => return ptr::null_mut(),
+    };
+    let chunks = (*model)
+        .parse_iter(input)
+        // a chunk of a NUL-terminated string never contains NUL.
+        .map(|chunk| CString::new(chunk).unwrap().into_raw())
+        .collect::<Box<[_]>>();
+    *out_count = chunks.len();
+    Box::into_raw(chunks) as *mut *mut c_char
+}
+
+/// budoux_free_result frees result returned by `budoux_parse`.
+///
+/// # Safety
+///
+/// `result` must be null or returned by `budoux_parse` with `count` strings,
+/// and must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn budoux_free_result(result: *mut *mut c_char, count: usize) {
+    if result.is_null() {
+        return;
+    }
+    let chunks = Box::from_raw(ptr::slice_from_raw_parts_mut(result, count));
+    for &chunk in chunks.iter() {
+        drop(CString::from_raw(chunk));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice;
+
+    use super::*;
+
+    unsafe fn parse(model: *const Model, input: &CStr) -> Vec<String> {
+        let mut count = 0;
+        let result = budoux_parse(model, input.as_ptr(), &mut count);
+        assert!(!result.is_null());
+        let chunks = slice::from_raw_parts(result, count)
+            .iter()
+            .map(|&chunk| CStr::from_ptr(chunk).to_str().unwrap().to_owned())
+            .collect();
+        budoux_free_result(result, count);
+        chunks
+    }
+
+    #[test]
+    fn test_parse() {
+        unsafe {
+            let m = budoux_default_japanese();
+
+            assert_eq!(parse(m, c""), Vec::<String>::new());
+            assert_eq!(
+                parse(m, c"これはテストです。"),
+                vec!["これは", "テストです。"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        unsafe {
+            let mut count = 0;
+
+            assert!(budoux_parse(ptr::null(), c"".as_ptr(), &mut count).is_null());
+            assert!(budoux_parse(budoux_default_thai(), ptr::null(), &mut count).is_null());
+            assert!(budoux_parse(budoux_default_thai(), c"".as_ptr(), ptr::null_mut()).is_null());
+            assert!(budoux_parse(budoux_default_thai(), c"\xff".as_ptr(), &mut count).is_null());
+            budoux_free_result(ptr::null_mut(), 0);
+        }
+    }
+
+    #[test]
+    fn test_model_from_json() {
+        unsafe {
+            let m = budoux_model_from_json(c"{\"UW4\": {\"油\": 10000}}".as_ptr());
+            assert!(!m.is_null());
+            assert_eq!(parse(m, c"水と油"), vec!["水と", "油"]);
+            budoux_model_free(m);
+
+            assert!(budoux_model_from_json(ptr::null()).is_null());
+            assert!(budoux_model_from_json(c"{".as_ptr()).is_null());
+            budoux_model_free(ptr::null_mut());
+        }
+    }
+}
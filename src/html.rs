@@ -0,0 +1,439 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::Model;
+
+/// WBR is the word break opportunity element inserted at boundaries.
+const WBR: &str = "<wbr>";
+
+/// INLINE_ELEMENTS is the elements whose text is parsed together with the surrounding text.
+///
+/// Other elements, e.g. `<p>` or `<br>`, separate the text before and after them.
+const INLINE_ELEMENTS: [&str; 25] = [
+    "a", "abbr", "b", "bdi", "bdo", "cite", "code", "data", "dfn", "em", "i", "kbd", "mark", "q",
+    "ruby", "s", "samp", "small", "span", "strong", "sub", "sup", "time", "u", "wbr",
+];
+
+/// RAW_TEXT_ELEMENTS is the elements whose content is not html, and is never parsed.
+const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+/// Token is a lexical unit of html.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    /// Text is character data between markups.
+    Text(&'a str),
+    /// Markup is a tag, comment, doctype or processing instruction, including the brackets.
+    Markup(&'a str),
+    /// Raw is the content of a raw text element such as `<script>`.
+    Raw(&'a str),
+}
+
+/// tokens splits html into text and markup tokens.
+fn tokens(html: &str) -> impl Iterator<Item = Token<'_>> {
+    let mut pos = 0;
+    // raw is the name of the raw text element whose content follows.
+    let mut raw = None;
+    core::iter::from_fn(move || loop {
+        let rest = &html[pos..];
+        if rest.is_empty() {
+            return None;
+        }
+        if let Some(name) = raw.take() {
+            let len = raw_text_len(rest, name);
+            pos += len;
+            if len == 0 {
+                continue;
+            }
+            return Some(Token::Raw(&rest[..len]));
+        }
+        let (token, len) = match markup_len(rest) {
+            Some(len) => {
+                let markup = &rest[..len];
+                raw = tag_name(markup).filter(|name| {
+                    !markup.starts_with("</")
+                        && !markup.ends_with("/>")
+                        && RAW_TEXT_ELEMENTS
+                            .iter()
+                            .any(|e| name.eq_ignore_ascii_case(e))
+                });
+                (Token::Markup(markup), len)
+            }
+            None => {
+                // text continues until the next markup.
+                let len = rest
+                    .char_indices()
+                    .skip(1)
+                    .find(|&(i, c)| c == '<' && markup_len(&rest[i..]).is_some())
+                    .map_or(rest.len(), |(i, _)| i);
+                (Token::Text(&rest[..len]), len)
+            }
+        };
+        pos += len;
+        return Some(token);
+    })
+}
+
+/// raw_text_len returns the length of the content of the raw text element `name` at the start of s,
+/// which ends at the end tag of the element.
+fn raw_text_len(s: &str, name: &str) -> usize {
+    s.match_indices("</")
+        .find(|&(i, _)| {
+            s.get(i + 2..i + 2 + name.len())
+                .is_some_and(|tag| tag.eq_ignore_ascii_case(name))
+        })
+        .map_or(s.len(), |(i, _)| i)
+}
+
+/// tag_name returns the element name of the start or end tag markup.
+fn tag_name(markup: &str) -> Option<&str> {
+    let name = markup.strip_prefix('<')?;
+    let name = name.strip_prefix('/').unwrap_or(name);
+    let len = name.bytes().take_while(u8::is_ascii_alphanumeric).count();
+    (len > 0).then(|| &name[..len])
+}
+
+/// is_inline returns whether the markup is transparent to text, i.e. a comment or an inline element.
+fn is_inline(markup: &str) -> bool {
+    markup.starts_with("<!--")
+        || tag_name(markup)
+            .is_some_and(|name| INLINE_ELEMENTS.iter().any(|e| name.eq_ignore_ascii_case(e)))
+}
+
+/// is_wbr returns whether the markup is a `<wbr>` tag.
+fn is_wbr(markup: &str) -> bool {
+    !markup.starts_with("</")
+        && tag_name(markup).is_some_and(|name| name.eq_ignore_ascii_case("wbr"))
+}
+
+/// markup_len returns the length of the markup at the start of s.
+///
+/// Unterminated markups extend to the end of s.
+fn markup_len(s: &str) -> Option<usize> {
+    let bytes = s.as_bytes();
+    if bytes.first() != Some(&b'<') {
+        return None;
+    }
+    if let Some(comment) = s.strip_prefix("<!--") {
+        return Some(comment.find("-->").map_or(s.len(), |i| i + 7));
+    }
+    match bytes.get(1) {
+        Some(c) if c.is_ascii_alphabetic() || matches!(c, b'/' | b'!' | b'?') => {}
+        _ => return None,
+    }
+    let mut quote = None;
+    for (i, &c) in bytes.iter().enumerate().skip(1) {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == b'"' || c == b'\'' => quote = Some(c),
+            None if c == b'>' => return Some(i + 1),
+            None => {}
+        }
+    }
+    Some(s.len())
+}
+
+/// decode_entity returns the character referenced by the entity at the start of s and its length.
+///
+/// Unknown named entities decode to U+FFFD so that they are still treated as a single unit.
+fn decode_entity(s: &str) -> Option<(char, usize)> {
+    let end = s.bytes().take(32).position(|c| c == b';')?;
+    let name = s.get(1..end)?;
+    let c = if let Some(num) = name.strip_prefix('#') {
+        let code = match num.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => num.parse().ok()?,
+        };
+        char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+    } else if !name.is_empty() && name.bytes().all(|c| c.is_ascii_alphanumeric()) {
+        match name {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            "nbsp" => '\u{a0}',
+            _ => char::REPLACEMENT_CHARACTER,
+        }
+    } else {
+        return None;
+    };
+    Some((c, end + 1))
+}
+
+impl Model {
+    /// translate_html returns html with word break opportunities (`<wbr>`) inserted at boundaries.
+    ///
+    /// Only text is parsed, and tags, attributes and comments are left untouched.
+    /// Text split by inline elements such as `<b>` or `<a>` is parsed as a whole, while other
+    /// elements such as `<p>` or `<br>` separate the text. A boundary across inline elements is
+    /// inserted before their tags, unless there is a `<wbr>` already, and the content of `<script>`
+    /// and `<style>` is kept as is.
+    /// Character references such as `&amp;` are treated as a single character.
+    ///
+    /// * `html` - input html.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let html = model.translate_html("<p class=\"a\">これは<b>テスト</b>です。</p>");
+    ///
+    /// assert_eq!(html, "<p class=\"a\">これは<wbr><b>テスト</b>です。</p>");
+    ///
+    /// let html = model.translate_html("<p>今日は<b>とても天気</b>です。</p>");
+    ///
+    /// assert_eq!(html, "<p>今日は<wbr><b>とても<wbr>天気</b>です。</p>");
+    /// ```
+    pub fn translate_html(&self, html: &str) -> String {
+        let mut out = String::with_capacity(html.len());
+        // run is the text and inline markups parsed together.
+        let mut run = Vec::new();
+        for token in tokens(html) {
+            match token {
+                Token::Text(_) => run.push(token),
+                Token::Markup(markup) if is_inline(markup) => run.push(token),
+                Token::Markup(s) | Token::Raw(s) => {
+                    self.translate_run(&run, &mut out);
+                    run.clear();
+                    out.push_str(s);
+                }
+            }
+        }
+        self.translate_run(&run, &mut out);
+        out
+    }
+
+    /// translate_run pushes the tokens of run into out, with `<wbr>` inserted at the boundaries of
+    /// the text of run.
+    fn translate_run(&self, run: &[Token<'_>], out: &mut String) {
+        // chars maps the byte offset of each decoded character to its text token and the byte
+        // offset in the token.
+        let mut decoded = String::new();
+        let mut chars = Vec::new();
+        for (t, token) in run.iter().enumerate() {
+            let Token::Text(text) = *token else {
+                continue;
+            };
+            let mut pos = 0;
+            while let Some(c) = text[pos..].chars().next() {
+                let (c, len) = match c {
+                    '&' => decode_entity(&text[pos..]).unwrap_or(('&', 1)),
+                    c => (c, c.len_utf8()),
+                };
+                chars.push((decoded.len(), t, pos));
+                decoded.push(c);
+                pos += len;
+            }
+        }
+
+        let mut breaks = Vec::new();
+        for boundary in self.parse_boundaries(&decoded) {
+            let k = chars.partition_point(|&(decoded, _, _)| decoded < boundary);
+            let (_, t, pos) = chars[k];
+            let prev = chars[k - 1].1;
+            if prev == t {
+                breaks.push((t, pos));
+                continue;
+            }
+            // a boundary across inline markups is inserted at the end of the preceding text, unless
+            // there is a <wbr> already.
+            let has_wbr = run[prev + 1..t]
+                .iter()
+                .any(|token| matches!(*token, Token::Markup(markup) if is_wbr(markup)));
+            if let (false, Token::Text(text)) = (has_wbr, run[prev]) {
+                breaks.push((prev, text.len()));
+            }
+        }
+
+        let mut breaks = breaks.into_iter().peekable();
+        for (t, token) in run.iter().enumerate() {
+            match *token {
+                Token::Text(text) => {
+                    let mut start = 0;
+                    while let Some((_, pos)) = breaks.next_if(|&(bt, _)| bt == t) {
+                        out.push_str(&text[start..pos]);
+                        out.push_str(WBR);
+                        start = pos;
+                    }
+                    out.push_str(&text[start..]);
+                }
+                Token::Markup(s) | Token::Raw(s) => out.push_str(s),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_entity, is_inline, is_wbr, tag_name, tokens, Token};
+
+    #[test]
+    fn test_tokens() {
+        assert_eq!(tokens("").collect::<Vec<_>>(), vec![]);
+        assert_eq!(
+            tokens("a<b title='>'>c</b><!-- <d> -->1 < 2<").collect::<Vec<_>>(),
+            vec![
+                Token::Text("a"),
+                Token::Markup("<b title='>'>"),
+                Token::Text("c"),
+                Token::Markup("</b>"),
+                Token::Markup("<!-- <d> -->"),
+                Token::Text("1 < 2<"),
+            ]
+        );
+        assert_eq!(
+            tokens("a<b").collect::<Vec<_>>(),
+            vec![Token::Text("a"), Token::Markup("<b")]
+        );
+    }
+
+    #[test]
+    fn test_tokens_raw_text() {
+        assert_eq!(
+            tokens("<script>if (a<b) {}</script>x<STYLE>p>a{}</Style><script/>y")
+                .collect::<Vec<_>>(),
+            vec![
+                Token::Markup("<script>"),
+                Token::Raw("if (a<b) {}"),
+                Token::Markup("</script>"),
+                Token::Text("x"),
+                Token::Markup("<STYLE>"),
+                Token::Raw("p>a{}"),
+                Token::Markup("</Style>"),
+                Token::Markup("<script/>"),
+                Token::Text("y"),
+            ]
+        );
+        assert_eq!(
+            tokens("<script></script><style>a").collect::<Vec<_>>(),
+            vec![
+                Token::Markup("<script>"),
+                Token::Markup("</script>"),
+                Token::Markup("<style>"),
+                Token::Raw("a"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tag_name() {
+        assert_eq!(tag_name("<b>"), Some("b"));
+        assert_eq!(tag_name("</SPAN>"), Some("SPAN"));
+        assert_eq!(tag_name("<br/>"), Some("br"));
+        assert_eq!(tag_name("<a href=\"x\">"), Some("a"));
+        assert_eq!(tag_name("<!-- a -->"), None);
+        assert_eq!(tag_name("<!DOCTYPE html>"), None);
+
+        assert!(is_inline("<b>"));
+        assert!(is_inline("</A>"));
+        assert!(is_inline("<!-- a -->"));
+        assert!(!is_inline("<p>"));
+        assert!(!is_inline("<br>"));
+        assert!(!is_inline("<bdox>"));
+
+        assert!(is_wbr("<wbr>"));
+        assert!(is_wbr("<WBR/>"));
+        assert!(!is_wbr("</wbr>"));
+        assert!(!is_wbr("<b>"));
+    }
+
+    #[test]
+    fn test_decode_entity() {
+        assert_eq!(decode_entity("&amp;"), Some(('&', 5)));
+        assert_eq!(decode_entity("&lt;b"), Some(('<', 4)));
+        assert_eq!(decode_entity("&#12354;"), Some(('あ', 8)));
+        assert_eq!(decode_entity("&#x3042;"), Some(('あ', 8)));
+        assert_eq!(decode_entity("&hellip;"), Some(('\u{fffd}', 8)));
+        assert_eq!(decode_entity("&"), None);
+        assert_eq!(decode_entity("& b;"), None);
+        assert_eq!(decode_entity("&;"), None);
+    }
+
+    #[test]
+    fn test_translate_html() {
+        let m = crate::models::default_japanese_model();
+
+        assert_eq!(m.translate_html(""), "");
+        assert_eq!(
+            m.translate_html("これはテストです。"),
+            "これは<wbr>テストです。"
+        );
+        assert_eq!(
+            m.translate_html("<a href=\"これはテストです。\">これはテストです。</a>"),
+            "<a href=\"これはテストです。\">これは<wbr>テストです。</a>"
+        );
+        assert_eq!(
+            m.translate_html("<!-- これはテストです。 -->"),
+            "<!-- これはテストです。 -->"
+        );
+    }
+
+    #[test]
+    fn test_translate_html_nested() {
+        let m = crate::models::default_japanese_model();
+
+        // text across inline elements is parsed as a whole.
+        let html = "<p>今日は<b>とても<a href=\"https://example.com/\">天気</a></b>です。</p>";
+        assert_eq!(
+            m.translate_html(html),
+            "<p>今日は<wbr><b>とても<wbr><a href=\"https://example.com/\">天気</a></b>です。</p>"
+        );
+        assert_eq!(
+            m.translate_html("<span>今日はとても天気です。</span>"),
+            "<span>今日は<wbr>とても<wbr>天気です。</span>"
+        );
+        // block and void elements separate the text.
+        assert_eq!(
+            m.translate_html("<div>今日は</div><div>とても天気です。</div>"),
+            "<div>今日は</div><div>とても<wbr>天気です。</div>"
+        );
+        assert_eq!(
+            m.translate_html("今日はとても<br>天気です。<img src=\"a.png\"/>水と油"),
+            "今日は<wbr>とても<br>天気です。<img src=\"a.png\"/>水と<wbr>油"
+        );
+        // existing <wbr> is not duplicated.
+        assert_eq!(
+            m.translate_html("これは<wbr>テストです。"),
+            "これは<wbr>テストです。"
+        );
+        assert_eq!(
+            m.translate_html("これは<WBR/><b>テスト</b>です。"),
+            "これは<WBR/><b>テスト</b>です。"
+        );
+        assert_eq!(
+            m.translate_html("これは<!-- a --></b>テストです。"),
+            "これは<wbr><!-- a --></b>テストです。"
+        );
+    }
+
+    #[test]
+    fn test_translate_html_raw_text() {
+        let m = crate::models::default_japanese_model();
+
+        for html in [
+            "<script>const s = \"これはテストです。\";</script>",
+            "<style>p::after { content: \"これはテストです。\" }</style>",
+            "<SCRIPT type=\"module\">if (a<b) { alert(\"これはテストです。\") }</SCRIPT>",
+        ] {
+            assert_eq!(m.translate_html(html), html);
+        }
+        assert_eq!(
+            m.translate_html("<script>x</script>これはテストです。"),
+            "<script>x</script>これは<wbr>テストです。"
+        );
+    }
+
+    #[test]
+    fn test_translate_html_entity() {
+        let m = crate::models::default_japanese_model();
+
+        for html in ["水&amp;油", "水&#12392;油", "水&lt;油"] {
+            let translated = m.translate_html(html);
+            assert_eq!(translated.replace("<wbr>", ""), html);
+            for entity in ["&amp;", "&#12392;", "&lt;"] {
+                assert_eq!(html.contains(entity), translated.contains(entity));
+            }
+        }
+        assert_eq!(m.translate_html("水&#12392;油"), "水&#12392;<wbr>油");
+    }
+}
@@ -6,53 +6,810 @@
 //! This project contains the deliverables of the [BudouX](https://github.com/google/budoux) project.
 //!
 //! Note:
-//! BudouX-rs supported plain text only, not supports html inputs.
+//! BudouX-rs supported plain text only, html inputs are supported with the `html` feature.
+//!
+//! # Features
+//!
+//! * `std` (default) - enables `std::io` based APIs such as `Model::from_reader`, and `ParserPool`.
+//!   Without it the crate is `no_std` and requires only `alloc`. In that case the model map
+//!   is a `BTreeMap` instead of a `HashMap`, and the bundled models are initialized through
+//!   [critical-section](https://crates.io/crates/critical-section), so the target must provide
+//!   a critical section implementation, unless the `race` feature is enabled.
+//! * `race` - initializes the bundled models lock-free with `once_cell::race::OnceBox` instead
+//!   of `once_cell::sync::OnceCell`, which saves the lock on single-threaded targets such as wasm.
+//!   Threads racing on the first use may each build the model.
+//! * `rayon` - parses batches in parallel in `Model::parse_batch`. Implies `std`.
+//! * `unicode-segmentation` - enables `Model::parse_graphemes` which never splits grapheme clusters.
+//! * `unicode-width` - enables `Model::parse_columns` which bounds the display width of chunks.
+//! * `smallvec` - enables `Model::parse_small` which keeps up to 4 chunks without heap allocation.
+//! * `html` - enables `Model::translate_html` which inserts `<wbr>` into html.
+//! * `ffi` - enables C bindings in the `ffi` module.
+//! * `wasm` - enables JavaScript bindings in the `wasm` module. Implies `std`.
+//! * `flate2` - enables `Model::from_gz_reader` which loads gzip compressed model json. Implies `std`.
+//! * `zstd` - enables `Model::from_zst_reader` which loads zstd compressed model json. Implies `std`.
+//! * `bincode` - enables `Model::to_bincode` and `Model::from_bincode`, a compact binary model
+//!   format which loads faster than json. Implies `std`.
+//! * `cli` - builds the `budoux` command which splits lines read from stdin. Implies `std`.
+//! * `lru` - enables `CachingModel` which memoizes parse results in an LRU cache. Implies `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
-use std::{collections::HashMap, ops::Range};
+use alloc::{borrow::Cow, string::String, vec, vec::Vec};
+use core::ops::Range;
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as HashMap;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
 
 /// models provides trained machine learning model.
 pub mod models;
 
+mod builder;
+#[cfg(feature = "lru")]
+mod cache;
+mod diff;
+mod explain;
+mod mask;
+mod options;
+mod parser;
+#[cfg(feature = "std")]
+mod pool;
+mod script;
+mod segmenter;
+#[cfg(feature = "std")]
+mod stream;
+
+pub use builder::ModelBuilder;
+#[cfg(feature = "lru")]
+pub use cache::CachingModel;
+pub use diff::FeatureDiff;
+pub use explain::BoundaryExplanation;
+pub use mask::FeatureMask;
+pub use options::ParseOptions;
+pub use parser::Parser;
+#[cfg(feature = "std")]
+pub use pool::{ParserPool, PooledParser};
+pub use script::{script_runs, Script};
+pub use segmenter::Segmenter;
+
+#[cfg(feature = "html")]
+mod html;
+
+/// ffi provides C bindings.
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+/// wasm provides JavaScript bindings.
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
 /// DEFAULT_THRESHOLD is default threshold for splitting a sentences.
-pub const DEFAULT_THRESHOLD: i32 = 1000;
+///
+/// A boundary is inserted where the score reaches the threshold, so the default
+/// reproduces the `score > 0` decision of the original BudouX.
+pub const DEFAULT_THRESHOLD: i32 = 1;
+
+/// SCORE_MULTIPLIER is default multiplier of feature scores, see `Model::with_score_multiplier`.
+///
+/// The original BudouX starts every boundary from minus half the sum of all feature scores.
+/// Multiplying both sides by 2 keeps the same decisions with integer scores only.
+pub const SCORE_MULTIPLIER: i32 = 2;
+
+/// DEFAULT_VISUALIZE_SEPARATOR is default separator of `Model::parse_visualized`.
+pub const DEFAULT_VISUALIZE_SEPARATOR: &str = "|";
+
+/// DEFAULT_PUNCTUATION is default punctuation detached by `ParseOptions::detach_punctuation`.
+pub const DEFAULT_PUNCTUATION: &str = "。、，．！？：；";
+
+/// DEFAULT_CLOSING_PUNCTUATION is default closing punctuation never placed at the start of a chunk
+/// by `ParseOptions::no_break_before_closing`.
+pub const DEFAULT_CLOSING_PUNCTUATION: &str = "」』）］｝〕〉》】〙〗〟’”｠»)]}";
+
+/// DEFAULT_KINSOKU_NOT_AT_START is default characters which must not start a chunk in
+/// `ParseOptions::kinsoku`, based on JIS X 4051: closing brackets, hyphens, iteration marks,
+/// the prolonged sound mark, small kana and punctuation.
+pub const DEFAULT_KINSOKU_NOT_AT_START: &str = concat!(
+    "」』）］｝〕〉》】〙〗〟’”｠»)]}",
+    "‐゠–〜",
+    "ヽヾゝゞ々〻ー",
+    "ぁぃぅぇぉっゃゅょゎゕゖァィゥェォッャュョヮヵヶㇰㇱㇲㇳㇴㇵㇶㇷㇸㇹㇺㇻㇼㇽㇾㇿ",
+    "、。，．・：；！？‼⁇⁈⁉,.:;!?",
+);
+
+/// DEFAULT_KINSOKU_NOT_AT_END is default characters which must not end a chunk in
+/// `ParseOptions::kinsoku`, based on JIS X 4051: opening brackets.
+pub const DEFAULT_KINSOKU_NOT_AT_END: &str = "「『（［｛〔〈《【〘〖〝‘“｟«([{";
+
+/// FEATURE_GROUPS is the feature groups recognized by the model.
+///
+/// Each entry is the group name, the offset of the first character of the feature from the
+/// character after the boundary, and the number of characters of the feature. The score of a
+/// boundary is the sum of the scores of the features which fit in input.
+const FEATURE_GROUPS: [(&str, isize, usize); 13] = [
+    ("UW1", -3, 1),
+    ("UW2", -2, 1),
+    ("UW3", -1, 1),
+    ("UW4", 0, 1),
+    ("UW5", 1, 1),
+    ("UW6", 2, 1),
+    ("BW1", -2, 2),
+    ("BW2", -1, 2),
+    ("BW3", 0, 2),
+    ("TW1", -3, 3),
+    ("TW2", -2, 3),
+    ("TW3", -1, 3),
+    ("TW4", 0, 3),
+];
+
+/// ModelError is error type of model validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelError {
+    /// UnknownFeature is the feature group not recognized by the model.
+    UnknownFeature(String),
+}
+
+impl core::fmt::Display for ModelError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ModelError::UnknownFeature(key) => write!(f, "unknown feature group: {}", key),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ModelError {}
+
+/// Error is error type of loading a model.
+#[derive(Debug)]
+pub enum Error {
+    /// Io is the error of reading the model.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    /// Json is the error of deserializing malformed model json.
+    Json(serde_json::Error),
+    /// InvalidModel is the error of well-formed json which is not a valid model.
+    InvalidModel(ModelError),
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            Error::Io(e) => write!(f, "read model: {}", e),
+            Error::Json(e) => write!(f, "parse model json: {}", e),
+            Error::InvalidModel(e) => write!(f, "invalid model: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Json(e) => Some(e),
+            Error::InvalidModel(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        // serde_json wraps the errors of the reader, which are reported as io errors.
+        #[cfg(feature = "std")]
+        if e.is_io() {
+            return Error::Io(e.into());
+        }
+        Error::Json(e)
+    }
+}
+
+impl From<ModelError> for Error {
+    fn from(e: ModelError) -> Self {
+        Error::InvalidModel(e)
+    }
+}
+
+/// ParseError is error type of `Model::try_parse`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// InvalidBoundary is the byte offset of a boundary which is out of order, out of range or
+    /// not on a `char` boundary of input.
+    InvalidBoundary(usize),
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseError::InvalidBoundary(offset) => write!(f, "invalid boundary at byte {}", offset),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// REGION_SCRIPTS is the script implied by a region subtag, for languages whose models are per
+/// script.
+const REGION_SCRIPTS: &[(&str, &str)] = &[
+    ("CN", "Hans"),
+    ("SG", "Hans"),
+    ("TW", "Hant"),
+    ("HK", "Hant"),
+    ("MO", "Hant"),
+];
 
 /// Model is type of trained machine learning model.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Model {
     map: HashMap<String, HashMap<String, i32>>,
-    base_score: i32,
+    // scores are accumulated in i64, which cannot overflow for fewer than 2^31 features.
+    base_score: i64,
+    // ascii_max_score is an upper bound of the score of any boundary in ASCII only input.
+    ascii_max_score: i64,
+    score_multiplier: i32,
 }
 
 impl Model {
     /// Creates a new `Model` instance from a nested `HashMap` and calculates the base score.
     /// The input `HashMap` must have the same structure as BudouX model json files.
     pub fn new(map: HashMap<String, HashMap<String, i32>>) -> Self {
-        let base_score = -map
+        let mut model = Self {
+            map,
+            base_score: 0,
+            ascii_max_score: 0,
+            score_multiplier: SCORE_MULTIPLIER,
+        };
+        model.recompute_base_score();
+        model
+    }
+
+    /// from_features creates a new `Model` instance from the static feature map generated by the
+    /// build script, without parsing json.
+    pub(crate) fn from_features(features: &[(&str, &[(&str, i32)])]) -> Self {
+        Self::new(
+            features
+                .iter()
+                .map(|&(group, features)| {
+                    let features = features
+                        .iter()
+                        .map(|&(key, score)| (String::from(key), score))
+                        .collect();
+                    (String::from(group), features)
+                })
+                .collect(),
+        )
+    }
+
+    /// with_score_multiplier returns the model whose feature scores are multiplied by `multiplier`
+    /// instead of `SCORE_MULTIPLIER`.
+    ///
+    /// The base score is minus half the sum of all feature scores times the multiplier, so every
+    /// score is the score of the original BudouX times the multiplier. A model whose scores are
+    /// already doubled gives the same scores as the original model with a multiplier of 1.
+    /// The multiplier is not serialized by `to_string` or `to_writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::Model::from_str(r#"{"UW4": {"油": 1000}}"#).unwrap();
+    /// let doubled = budoux::Model::from_str(r#"{"UW4": {"油": 2000}}"#)
+    ///     .unwrap()
+    ///     .with_score_multiplier(1);
+    ///
+    /// assert_eq!(doubled.score_multiplier(), 1);
+    /// assert_eq!(doubled.base_score(), model.base_score());
+    /// assert_eq!(doubled.scores("水と油"), model.scores("水と油"));
+    /// ```
+    pub fn with_score_multiplier(mut self, multiplier: i32) -> Self {
+        self.score_multiplier = multiplier;
+        self.recompute_base_score();
+        self
+    }
+
+    /// score_multiplier returns the multiplier of feature scores, which is `SCORE_MULTIPLIER`
+    /// unless changed by `with_score_multiplier`.
+    pub fn score_multiplier(&self) -> i32 {
+        self.score_multiplier
+    }
+
+    fn compute_base_score(map: &HashMap<String, HashMap<String, i32>>, multiplier: i32) -> i64 {
+        let sum = map
             .values()
-            .into_iter()
-            .map(|v| v.values().copied().sum::<i32>())
-            .sum::<i32>();
-        Self { map, base_score }
+            .flat_map(|v| v.values())
+            .map(|&score| i64::from(score))
+            .sum::<i64>();
+        // upstream breaks where the exact score is positive. The exact score of an odd product is
+        // a half-integer, so the base score is rounded up to break at the same boundaries with the
+        // threshold of 1.
+        (1 - sum * i64::from(multiplier)).div_euclid(2)
+    }
+
+    /// compute_ascii_max_score returns the base score plus the largest score each feature group can
+    /// contribute with an ASCII key. Only ASCII keys match ASCII input, so no boundary of ASCII
+    /// input scores more than this.
+    fn compute_ascii_max_score(
+        map: &HashMap<String, HashMap<String, i32>>,
+        base_score: i64,
+        multiplier: i32,
+    ) -> i64 {
+        let features = map.values().map(|features| {
+            let max = features
+                .iter()
+                .filter(|(key, _)| key.is_ascii())
+                .map(|(_, &score)| i64::from(score) * i64::from(multiplier))
+                .max();
+            max.unwrap_or(0).max(0)
+        });
+        base_score + features.sum::<i64>()
+    }
+
+    /// never_breaks_ascii returns whether no boundary of input reaches threshold because input is
+    /// ASCII only and no ASCII feature can raise the score enough. Scoring is skipped in that case.
+    pub(crate) fn never_breaks_ascii(&self, input: &str, threshold: i32) -> bool {
+        self.ascii_max_score < i64::from(threshold) && input.is_ascii()
+    }
+
+    /// Creates a new `Model` instance from a nested `BTreeMap` and calculates the base score.
+    /// The input `BTreeMap` must have the same structure as BudouX model json files.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut uw4 = BTreeMap::new();
+    /// uw4.insert("油".to_string(), 1000);
+    /// let mut map = BTreeMap::new();
+    /// map.insert("UW4".to_string(), uw4);
+    /// let model = budoux::Model::from_btree(map);
+    ///
+    /// assert_eq!(model.parse("水と油"), vec!["水と", "油"]);
+    /// ```
+    pub fn from_btree(
+        map: alloc::collections::BTreeMap<String, alloc::collections::BTreeMap<String, i32>>,
+    ) -> Self {
+        Self::new(
+            map.into_iter()
+                .map(|(group, features)| (group, features.into_iter().collect()))
+                .collect(),
+        )
+    }
+
+    /// for_language returns the bundled model for the BCP 47 language tag, or `None` if there is no
+    /// model for the language.
+    ///
+    /// Tags are matched case insensitively, and both `-` and `_` are accepted as separators.
+    /// Chinese requires a script (`zh-Hans`, `zh-Hant`) or a region (`zh-CN`, `zh-TW`, ...) subtag.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use budoux::{models, Model};
+    ///
+    /// assert_eq!(Model::for_language("ja"), Some(models::default_japanese_model()));
+    /// assert_eq!(Model::for_language("zh_Hant"), Some(models::default_traditional_chinese_model()));
+    /// assert_eq!(Model::for_language("en"), None);
+    /// ```
+    pub fn for_language(tag: &str) -> Option<&'static Model> {
+        let mut subtags = tag.split(['-', '_']);
+        let language = subtags.next()?;
+        let find = |matches: &dyn Fn(&str) -> bool| {
+            models::MODELS
+                .iter()
+                .find(|(tag, _)| matches(tag))
+                .map(|&(_, model)| model())
+        };
+        if let Some(model) = find(&|tag| tag.eq_ignore_ascii_case(language)) {
+            return Some(model);
+        }
+        // the script subtag precedes the region subtag, so it takes precedence.
+        subtags.find_map(|subtag| {
+            let script = REGION_SCRIPTS
+                .iter()
+                .find(|(region, _)| subtag.eq_ignore_ascii_case(region))
+                .map_or(subtag, |&(_, script)| script);
+            find(&|tag| {
+                tag.split_once('-').is_some_and(|(l, s)| {
+                    l.eq_ignore_ascii_case(language) && s.eq_ignore_ascii_case(script)
+                })
+            })
+        })
     }
 
-    pub fn from_reader<R: std::io::Read>(reader: R) -> serde_json::Result<Self> {
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: std::io::Read>(reader: R) -> Result<Self, Error> {
         Ok(Self::new(serde_json::from_reader(reader)?))
     }
 
-    pub fn from_str(s: &str) -> serde_json::Result<Self> {
+    /// from_gz_reader decompresses gzip compressed model json from the reader and deserializes it.
+    #[cfg(feature = "flate2")]
+    pub fn from_gz_reader<R: std::io::Read>(reader: R) -> Result<Self, Error> {
+        Self::from_reader(flate2::read::GzDecoder::new(reader))
+    }
+
+    /// from_zst_reader decompresses zstd compressed model json from the reader and deserializes it.
+    #[cfg(feature = "zstd")]
+    pub fn from_zst_reader<R: std::io::Read>(reader: R) -> Result<Self, Error> {
+        Self::from_reader(zstd::Decoder::new(reader)?)
+    }
+
+    /// from_str deserializes the model from BudouX model json.
+    ///
+    /// This is the same as the `FromStr` implementation, which also allows `json.parse::<Model>()`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self, Error> {
         Ok(Self::new(serde_json::from_str(s)?))
     }
 
+    pub fn from_slice(v: &[u8]) -> Result<Self, Error> {
+        Ok(Self::new(serde_json::from_slice(v)?))
+    }
+
+    /// from_flat_json deserializes the model from flat json such as `{"UW1:字": 1}`, which is used by
+    /// older BudouX tooling, and builds the nested map.
+    ///
+    /// Each key is split at the first `:` into the group and the feature key. Scores of duplicated
+    /// features are summed. A key without `:` is `Error::InvalidModel`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::Model::from_flat_json(r#"{"UW4:油": 1000, "UW3::": 10}"#).unwrap();
+    ///
+    /// assert_eq!(model.as_inner()["UW4"]["油"], 1000);
+    /// assert_eq!(model.as_inner()["UW3"][":"], 10);
+    /// assert_eq!(model.parse("水と油"), vec!["水と", "油"]);
+    /// ```
+    pub fn from_flat_json(s: &str) -> Result<Self, Error> {
+        let flat: HashMap<String, i32> = serde_json::from_str(s)?;
+        flat.into_iter()
+            .map(|(name, score)| match name.split_once(':') {
+                Some((group, key)) => Ok((String::from(group), String::from(key), score)),
+                None => Err(ModelError::UnknownFeature(name).into()),
+            })
+            .collect()
+    }
+
+    /// from_float_json deserializes the model from BudouX model json with floating point scores,
+    /// multiplying each score by `scale` and rounding it to the nearest integer.
+    ///
+    /// Rounding loses up to 0.5 of each score, which is accumulated over the features of a
+    /// boundary, so `scale` should be large enough for the differences between scores to survive.
+    /// Scores out of the range of `i32` after scaling are clamped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::Model::from_float_json(r#"{"UW4": {"油": 0.25, "水": -0.0004}}"#, 1000).unwrap();
+    ///
+    /// assert_eq!(model.as_inner()["UW4"]["油"], 250);
+    /// assert_eq!(model.as_inner()["UW4"]["水"], 0);
+    /// assert_eq!(model.parse("水と油"), vec!["水と", "油"]);
+    /// ```
+    pub fn from_float_json(s: &str, scale: i32) -> Result<Self, Error> {
+        let map: HashMap<String, HashMap<String, f64>> = serde_json::from_str(s)?;
+        let scale = f64::from(scale);
+        Ok(Self::new(
+            map.into_iter()
+                .map(|(group, features)| {
+                    let features = features
+                        .into_iter()
+                        .map(|(key, score)| (key, round_score(score * scale)))
+                        .collect();
+                    (group, features)
+                })
+                .collect(),
+        ))
+    }
+
+    /// to_writer serializes the model as BudouX model json into the writer.
+    #[cfg(feature = "std")]
+    pub fn to_writer<W: std::io::Write>(&self, writer: W) -> serde_json::Result<()> {
+        serde_json::to_writer(writer, &self.map)
+    }
+
+    /// to_string serializes the model as BudouX model json.
+    pub fn to_string(&self) -> serde_json::Result<String> {
+        serde_json::to_string(&self.map)
+    }
+
+    /// to_bincode serializes the model into the compact binary format of
+    /// [bincode](https://crates.io/crates/bincode).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let bytes = model.to_bincode().unwrap();
+    ///
+    /// assert_eq!(&budoux::Model::from_bincode(&bytes).unwrap(), model);
+    /// ```
+    #[cfg(feature = "bincode")]
+    pub fn to_bincode(&self) -> bincode::Result<Vec<u8>> {
+        bincode::serialize(&self.map)
+    }
+
+    /// from_bincode deserializes the model serialized by `to_bincode` and calculates the base score.
+    #[cfg(feature = "bincode")]
+    pub fn from_bincode(bytes: &[u8]) -> bincode::Result<Self> {
+        Ok(Self::new(bincode::deserialize(bytes)?))
+    }
+
     pub fn as_inner(&self) -> &HashMap<String, HashMap<String, i32>> {
         &self.map
     }
 
+    /// as_inner_mut returns the mutable feature map for in-place tweaks.
+    ///
+    /// The base score is not updated by mutating the map, so `recompute_base_score` must be called
+    /// after the mutation. Until then the model gives different results from `Model::new` with
+    /// the same map.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut model = budoux::Model::from_str(r#"{"UW4": {"油": 1000}}"#).unwrap();
+    /// model.as_inner_mut().get_mut("UW4").unwrap().insert("水".to_string(), -1000);
+    /// model.recompute_base_score();
+    ///
+    /// assert_eq!(model.base_score(), 0);
+    /// ```
+    pub fn as_inner_mut(&mut self) -> &mut HashMap<String, HashMap<String, i32>> {
+        &mut self.map
+    }
+
+    /// recompute_base_score calculates the base score again from the feature map.
+    ///
+    /// It must be called after mutating the map through `as_inner_mut`.
+    pub fn recompute_base_score(&mut self) {
+        self.base_score = Self::compute_base_score(&self.map, self.score_multiplier);
+        self.ascii_max_score =
+            Self::compute_ascii_max_score(&self.map, self.base_score, self.score_multiplier);
+    }
+
+    /// feature_count returns the total number of features over all feature groups.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::Model::from_str(r#"{"UW1": {"a": 1, "b": 2}, "UW2": {"a": 3}}"#).unwrap();
+    ///
+    /// assert_eq!(model.feature_count(), 3);
+    /// ```
+    pub fn feature_count(&self) -> usize {
+        self.map.values().map(|features| features.len()).sum()
+    }
+
+    /// is_empty returns whether the model has no features.
+    ///
+    /// An empty model never inserts a break.
+    pub fn is_empty(&self) -> bool {
+        self.feature_count() == 0
+    }
+
+    /// memory_bytes returns an approximate size in bytes of the heap memory owned by the model.
+    ///
+    /// It is the sum of the allocated capacity of every group and key string, and the size of the
+    /// entries of every map. With `std`, unused slots of the hash maps and a control byte per slot
+    /// are counted as well. Allocator overhead and the internal nodes of `BTreeMap` are not counted,
+    /// so the actual usage is somewhat higher.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let ja = budoux::models::default_japanese_model();
+    /// let zh = budoux::models::default_simplified_chinese_model();
+    ///
+    /// assert!(ja.memory_bytes() < zh.memory_bytes());
+    /// ```
+    pub fn memory_bytes(&self) -> usize {
+        fn table_bytes<V>(map: &HashMap<String, V>) -> usize {
+            #[cfg(feature = "std")]
+            let slots = map.capacity() * (core::mem::size_of::<(String, V)>() + 1);
+            #[cfg(not(feature = "std"))]
+            let slots = map.len() * core::mem::size_of::<(String, V)>();
+            slots + map.keys().map(String::capacity).sum::<usize>()
+        }
+
+        table_bytes(&self.map) + self.map.values().map(table_bytes).sum::<usize>()
+    }
+
+    /// pruned returns a copy of the model without the features whose absolute score is below
+    /// `min_abs_score`, and recalculates the base score. Feature groups left without features are
+    /// removed as well.
+    ///
+    /// * `min_abs_score` - minimum absolute score of the features kept. Zero or negative keeps every feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let pruned = model.pruned(100);
+    ///
+    /// assert!(pruned.feature_count() < model.feature_count());
+    /// assert_eq!(pruned.parse("これはテストです。"), vec!["これは", "テストです。"]);
+    /// ```
+    pub fn pruned(&self, min_abs_score: i32) -> Model {
+        self.pruned_with_count(min_abs_score).0
+    }
+
+    /// pruned_with_count is like `pruned`, but also returns the number of removed features.
+    ///
+    /// * `min_abs_score` - minimum absolute score of the features kept. Zero or negative keeps every feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let (pruned, removed) = model.pruned_with_count(100);
+    ///
+    /// assert!(removed > 0);
+    /// assert_eq!(pruned.feature_count() + removed, model.feature_count());
+    /// ```
+    pub fn pruned_with_count(&self, min_abs_score: i32) -> (Model, usize) {
+        let min_abs_score = u32::try_from(min_abs_score).unwrap_or(0);
+        let mut removed = 0;
+        let map = self
+            .map
+            .iter()
+            .map(|(group, features)| {
+                let kept = features
+                    .iter()
+                    .filter(|(_, score)| score.unsigned_abs() >= min_abs_score)
+                    .map(|(key, &score)| (key.clone(), score))
+                    .collect::<HashMap<_, _>>();
+                removed += features.len() - kept.len();
+                (group.clone(), kept)
+            })
+            .filter(|(_, features)| !features.is_empty())
+            .collect();
+        (Model::new(map), removed)
+    }
+
+    /// content_hash returns a stable fingerprint of the features of the model.
+    ///
+    /// The hash is the 64-bit FNV-1a of the features sorted by group and key, so it is the same for
+    /// models with the same features regardless of the map order, process or platform, and it changes
+    /// when a feature changes. It is not a cryptographic hash.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let reloaded = budoux::Model::from_str(&model.to_string().unwrap()).unwrap();
+    ///
+    /// assert_eq!(model.content_hash(), reloaded.content_hash());
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let mut hash = OFFSET_BASIS;
+        let mut write = |bytes: &[u8]| {
+            for &b in bytes {
+                hash = (hash ^ u64::from(b)).wrapping_mul(PRIME);
+            }
+        };
+        for (group, key, score) in self.sorted_features() {
+            // 0xff never appears in UTF-8, so it separates the strings unambiguously.
+            write(group.as_bytes());
+            write(&[0xff]);
+            write(key.as_bytes());
+            write(&[0xff]);
+            write(&score.to_le_bytes());
+        }
+        hash
+    }
+
+    /// sorted_features returns every feature sorted by group and key.
+    fn sorted_features(&self) -> Vec<(&str, &str, i32)> {
+        let mut features = self
+            .map
+            .iter()
+            .flat_map(|(group, features)| {
+                features
+                    .iter()
+                    .map(move |(key, &score)| (group.as_str(), key.as_str(), score))
+            })
+            .collect::<Vec<_>>();
+        features.sort_unstable();
+        features
+    }
+
+    /// base_score returns the score every boundary starts from, which is the negated sum of all
+    /// feature scores times half the score multiplier, rounded up. The score of a boundary is
+    /// `base_score` plus the scores of its features times the score multiplier, which is
+    /// `SCORE_MULTIPLIER` (2) by default.
+    ///
+    /// Scores are accumulated without overflow and saturated to the range of `i32` when returned,
+    /// which does not change any comparison with an `i32` threshold.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let sum = model
+    ///     .as_inner()
+    ///     .values()
+    ///     .flat_map(|features| features.values())
+    ///     .sum::<i32>();
+    ///
+    /// assert_eq!(model.base_score(), -sum);
+    /// ```
+    pub fn base_score(&self) -> i32 {
+        saturate(self.base_score)
+    }
+
+    /// feature_groups returns the feature groups present in the model in sorted order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::Model::from_str(r#"{"UW2": {}, "BW1": {}}"#).unwrap();
+    ///
+    /// assert_eq!(model.feature_groups(), vec!["BW1", "UW2"]);
+    /// ```
+    pub fn feature_groups(&self) -> Vec<&str> {
+        let mut groups = self.map.keys().map(String::as_str).collect::<Vec<_>>();
+        groups.sort_unstable();
+        groups
+    }
+
+    /// validate checks that every feature group of the model is recognized.
+    ///
+    /// Unknown feature groups are silently ignored by `parse`, so this catches typos in hand-edited models.
+    /// When there are multiple unknown feature groups, the first one in sorted order is reported.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::Model::from_str(r#"{"UW7": {"あ": 1}}"#).unwrap();
+    ///
+    /// assert_eq!(
+    ///     model.validate(),
+    ///     Err(budoux::ModelError::UnknownFeature(String::from("UW7")))
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), ModelError> {
+        match self
+            .map
+            .keys()
+            .filter(|key| {
+                FEATURE_GROUPS
+                    .iter()
+                    .all(|&(group, _, _)| group != key.as_str())
+            })
+            .min()
+        {
+            Some(key) => Err(ModelError::UnknownFeature(key.clone())),
+            None => Ok(()),
+        }
+    }
+
     fn get(&self, key: &str) -> Option<&HashMap<String, i32>> {
         self.map.get(key)
     }
 
     /// parse returns splitted string slice from input.
     ///
+    /// Chunks are always split at `char` boundaries and are in logical (memory) order, even for
+    /// right-to-left scripts, so concatenating them reproduces input byte for byte.
+    /// Input without breakable content, such as `"\n"` or `"   "`, is returned as a single chunk.
+    /// Use `ParseOptions::skip_blank` to get no chunks instead.
+    /// A model may split a multi-scalar sequence such as a ZWJ emoji, so use `parse_graphemes` to
+    /// keep such sequences intact.
+    ///
     /// * `input` - input sentences.
     ///
     /// # Examples
@@ -77,135 +834,2961 @@ impl Model {
     /// assert_eq!(words, vec!["これは", "テストです。"]);
     /// ```
     pub fn parse<'i>(&self, input: &'i str) -> Vec<&'i str> {
-        if input.is_empty() {
-            return vec![];
-        }
-        let chars = input.char_indices().collect::<Vec<_>>();
-        fn to_range(i: (usize, char)) -> Range<usize> {
-            let (start, char) = i;
-            start..start + char.len_utf8()
-        }
-        fn merge_range(a: Range<usize>, b: Range<usize>) -> Range<usize> {
-            a.start.min(b.start)..a.end.max(b.end)
-        }
-        fn get_merged_range(start: usize, end: usize, chars: &[(usize, char)]) -> Range<usize> {
-            merge_range(to_range(chars[start]), to_range(chars[end - 1]))
-        }
-        fn get_score(model: &Model, key: &str, range: Range<usize>, text: &str) -> i32 {
-            model
-                .get(key)
-                .and_then(|v| v.get(&text[range]))
-                .copied()
-                .unwrap_or(0)
-                * 2
-        }
-        assert!(chars.len() > 0);
-        let mut chunks = vec![to_range(chars[0])];
-        for i in 1..chars.len() {
-            let mut score = self.base_score;
-            if i > 2 {
-                score += get_score(self, "UW1", to_range(chars[i - 3]), input);
-            }
-            if i > 1 {
-                score += get_score(self, "UW2", to_range(chars[i - 2]), input);
-            }
-            score += get_score(self, "UW3", to_range(chars[i - 1]), input);
-            score += get_score(self, "UW4", to_range(chars[i]), input);
-            if i + 1 < chars.len() {
-                score += get_score(self, "UW5", to_range(chars[i + 1]), input);
-            }
-            if i + 2 < chars.len() {
-                score += get_score(self, "UW6", to_range(chars[i + 2]), input);
-            }
-
-            if i > 1 {
-                score += get_score(self, "BW1", get_merged_range(i - 2, i, &chars), input);
-            }
-            score += get_score(self, "BW2", get_merged_range(i - 1, i, &chars), input);
-            if i + 1 < chars.len() {
-                score += get_score(self, "BW3", get_merged_range(i, i + 2, &chars), input);
-            }
-
-            if i > 2 {
-                score += get_score(self, "TW1", get_merged_range(i - 3, i, &chars), input);
-            }
-            if i > 1 {
-                score += get_score(self, "TW2", get_merged_range(i - 2, i + 1, &chars), input);
-            }
-            if i + 1 < chars.len() {
-                score += get_score(self, "TW3", get_merged_range(i - 1, i + 2, &chars), input);
-            }
-            if i + 2 < chars.len() {
-                score += get_score(self, "TW4", get_merged_range(i, i + 3, &chars), input);
-            }
-            if score > 0 {
-                chunks.push(to_range(chars[i]));
-            } else {
-                if let Some(last_range) = chunks.last_mut() {
-                    let cur_range = to_range(chars[i]);
-                    *last_range = merge_range(last_range.clone(), cur_range);
-                }
-            }
-        }
-        chunks.into_iter().map(|r| &input[r]).collect()
+        self.parse_with_threshold(input, DEFAULT_THRESHOLD)
     }
-}
 
-#[cfg(test)]
-mod tests {
+    /// try_parse returns splitted string slice from input like `parse`, but checks every boundary
+    /// instead of assuming it is valid.
+    ///
+    /// Boundaries are always valid for a `&str`, so this never fails unless there is a bug in the
+    /// boundary computation. It is a defensive alternative to `parse` for untrusted pipelines
+    /// which must not panic.
+    ///
+    /// * `input` - input sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    ///
+    /// assert_eq!(model.try_parse("水と油"), Ok(vec!["水と", "油"]));
+    /// ```
+    pub fn try_parse<'i>(&self, input: &'i str) -> Result<Vec<&'i str>, ParseError> {
+        try_split_chunks(input, self.boundary_iter(input, DEFAULT_THRESHOLD))
+    }
+
+    /// parse_small returns splitted string slice from input like `parse`, in a
+    /// [`SmallVec`](smallvec::SmallVec) which keeps up to 4 chunks inline, so that parsing short
+    /// input such as labels does not allocate the result on the heap.
+    ///
+    /// * `input` - input sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let words = model.parse_small("これはテストです。");
+    ///
+    /// assert_eq!(words.as_slice(), ["これは", "テストです。"]);
+    /// assert!(!words.spilled());
+    /// ```
+    #[cfg(feature = "smallvec")]
+    pub fn parse_small<'i>(&'i self, input: &'i str) -> smallvec::SmallVec<[&'i str; 4]> {
+        self.parse_iter(input).collect()
+    }
+
+    /// parse_owned returns splitted owned strings from input.
+    ///
+    /// Unlike `parse`, the result does not borrow from input.
+    ///
+    /// * `input` - input sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let words = model.parse_owned(&String::from("これはテストです。"));
+    ///
+    /// assert_eq!(words, vec![String::from("これは"), String::from("テストです。")]);
+    /// ```
+    pub fn parse_owned(&self, input: &str) -> Vec<String> {
+        self.parse_iter(input).map(String::from).collect()
+    }
+
+    /// parse_cow returns splitted strings from input, borrowed if input is borrowed and owned if
+    /// input is owned.
+    ///
+    /// Owned input is split in place from the end, so its buffer is reused for the first chunk and
+    /// each byte is copied at most once. The whole input is never cloned.
+    ///
+    /// * `input` - input sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::borrow::Cow;
+    ///
+    /// let model = budoux::models::default_japanese_model();
+    /// let borrowed = model.parse_cow(Cow::Borrowed("これはテストです。"));
+    /// let owned = model.parse_cow(Cow::Owned(String::from("これはテストです。")));
+    ///
+    /// assert!(matches!(borrowed[0], Cow::Borrowed("これは")));
+    /// assert!(matches!(&owned[1], Cow::Owned(s) if s == "テストです。"));
+    /// ```
+    pub fn parse_cow<'i>(&self, input: Cow<'i, str>) -> Vec<Cow<'i, str>> {
+        let mut input = match input {
+            Cow::Borrowed(input) => {
+                return self.parse(input).into_iter().map(Cow::Borrowed).collect()
+            }
+            Cow::Owned(input) => input,
+        };
+        let boundaries = self.parse_boundaries(&input);
+        let mut chunks = Vec::with_capacity(boundaries.len() + 1);
+        for &boundary in boundaries.iter().rev() {
+            chunks.push(Cow::Owned(input.split_off(boundary)));
+        }
+        if !input.is_empty() {
+            chunks.push(Cow::Owned(input));
+        }
+        chunks.reverse();
+        chunks
+    }
+
+    /// parse_with_threshold returns splitted string slice from input using the given threshold.
+    ///
+    /// * `input` - input sentences.
+    /// * `threshold` - a boundary is inserted where the score is greater than or equal to this value.
+    ///
+    /// # Examples
+    ///
+    /// Higher thresholds break less aggressively.
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let words = model.parse_with_threshold("これはテストです。", i32::MAX);
+    ///
+    /// assert_eq!(words, vec!["これはテストです。"]);
+    /// ```
+    pub fn parse_with_threshold<'i>(&self, input: &'i str, threshold: i32) -> Vec<&'i str> {
+        self.parse_with_options(
+            input,
+            &ParseOptions {
+                threshold,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// parse_with_options returns splitted string slice from input using the given options.
+    ///
+    /// * `input` - input sentences.
+    /// * `options` - parse options.
+    ///
+    /// # Examples
+    ///
+    /// Always break after newlines.
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let options = budoux::ParseOptions {
+    ///     hard_break_on_newline: true,
+    ///     ..Default::default()
+    /// };
+    /// let words = model.parse_with_options("これはテストです。\n今日は晴天です。", &options);
+    ///
+    /// assert_eq!(words, vec!["これは", "テストです。\n", "今日は", "晴天です。"]);
+    /// ```
+    pub fn parse_with_options<'i>(&self, input: &'i str, options: &ParseOptions) -> Vec<&'i str> {
+        self.parser().parse_with_options(input, options)
+    }
+
+    /// parse_ranges returns splitted string slice from input paired with its byte range in input.
+    ///
+    /// * `input` - input sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let words = model.parse_ranges("水と油");
+    ///
+    /// assert_eq!(words, vec![(0..6, "水と"), (6..9, "油")]);
+    /// ```
+    pub fn parse_ranges<'i>(&self, input: &'i str) -> Vec<(Range<usize>, &'i str)> {
+        self.parser().parse_ranges(input)
+    }
+
+    /// average_chunk_len returns the average number of characters per chunk over inputs using the given threshold.
+    ///
+    /// Returns 0 if there are no chunks. Together with `parse_with_threshold`, this is useful to search a
+    /// threshold which yields a target chunk length.
+    ///
+    /// * `inputs` - input sentences.
+    /// * `threshold` - a boundary is inserted where the score is greater than or equal to this value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    ///
+    /// assert_eq!(model.average_chunk_len(&["これはテストです。"], i32::MAX), 9.0);
+    /// assert_eq!(model.average_chunk_len(&["これはテストです。"], i32::MIN), 1.0);
+    /// ```
+    pub fn average_chunk_len(&self, inputs: &[&str], threshold: i32) -> f64 {
+        let (chars, chunks) = inputs.iter().filter(|input| !input.is_empty()).fold(
+            (0, 0),
+            |(chars, chunks), input| {
+                (
+                    chars + input.chars().count(),
+                    chunks + self.boundary_iter(input, threshold).count() + 1,
+                )
+            },
+        );
+        if chunks == 0 {
+            0.0
+        } else {
+            chars as f64 / chunks as f64
+        }
+    }
+
+    /// parse_into stores splitted string slice from input into `out`.
+    ///
+    /// `out` is cleared before parsing, so its allocation can be reused across calls.
+    ///
+    /// * `input` - input sentences.
+    /// * `out` - output buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let mut words = vec![];
+    ///
+    /// model.parse_into("これはテストです。", &mut words);
+    /// assert_eq!(words, vec!["これは", "テストです。"]);
+    ///
+    /// model.parse_into("水と油", &mut words);
+    /// assert_eq!(words, vec!["水と", "油"]);
+    /// ```
+    pub fn parse_into<'i>(&self, input: &'i str, out: &mut Vec<&'i str>) {
+        self.parser().parse_into(input, out);
+    }
+
+    /// parser returns a new `Parser` which reuses scratch buffers across calls.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let mut parser = model.parser();
+    ///
+    /// assert_eq!(parser.parse("これはテストです。"), vec!["これは", "テストです。"]);
+    /// ```
+    pub fn parser(&self) -> Parser<'_> {
+        Parser::new(self)
+    }
+
+    /// parse_boundaries returns byte offsets where a break is inserted.
+    ///
+    /// Each offset is the start of a chunk, except the first chunk which always starts at 0.
+    ///
+    /// * `input` - input sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let input = "これはテストです。";
+    /// let boundaries = model.parse_boundaries(input);
+    ///
+    /// assert_eq!(boundaries, vec![9]);
+    /// assert_eq!(&input[boundaries[0]..], "テストです。");
+    /// ```
+    pub fn parse_boundaries(&self, input: &str) -> Vec<usize> {
+        self.boundaries_with_threshold(input, DEFAULT_THRESHOLD)
+    }
+
+    /// parse_visit calls `f` with the byte range of each chunk of input in order, without allocation.
+    ///
+    /// The ranges are those of the chunks returned by `parse`.
+    ///
+    /// * `input` - input sentences.
+    /// * `f` - callback receiving the byte range of a chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let mut ranges = vec![];
+    /// model.parse_visit("これはテストです。", |range| ranges.push(range));
+    ///
+    /// assert_eq!(ranges, vec![0..9, 9..27]);
+    /// ```
+    pub fn parse_visit(&self, input: &str, mut f: impl FnMut(Range<usize>)) {
+        let mut start = 0;
+        for end in self.boundary_iter(input, DEFAULT_THRESHOLD) {
+            f(start..end);
+            start = end;
+        }
+        if !input.is_empty() {
+            f(start..input.len());
+        }
+    }
+
+    /// parse_iter returns an iterator over splitted string slice from input.
+    ///
+    /// Chunks are produced lazily, so breaking out of the loop early skips scoring the rest of the input.
+    ///
+    /// * `input` - input sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let mut words = model.parse_iter("これはテストです。");
+    ///
+    /// assert_eq!(words.next(), Some("これは"));
+    /// assert_eq!(words.next(), Some("テストです。"));
+    /// assert_eq!(words.next(), None);
+    /// ```
+    pub fn parse_iter<'i>(&'i self, input: &'i str) -> impl Iterator<Item = &'i str> {
+        split_chunks(input, self.boundary_iter(input, DEFAULT_THRESHOLD))
+    }
+
+    /// parse_graphemes returns splitted string slice from input, treating each extended grapheme cluster
+    /// as a single character.
+    ///
+    /// Unlike `parse`, a chunk never splits a grapheme cluster such as a ZWJ emoji sequence or a
+    /// character with combining marks.
+    ///
+    /// * `input` - input sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let words = model.parse_graphemes("これは👨‍👩‍👧‍👦です。");
+    ///
+    /// assert!(words.iter().any(|w| w.contains("👨‍👩‍👧‍👦")));
+    /// ```
+    #[cfg(feature = "unicode-segmentation")]
+    pub fn parse_graphemes<'i>(&self, input: &'i str) -> Vec<&'i str> {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        let boundaries = self
+            .unit_score_iter(input, input.grapheme_indices(true), FeatureMask::ALL)
+            .filter(|&(_, score)| score >= DEFAULT_THRESHOLD)
+            .map(|(offset, _)| offset);
+        split_chunks(input, boundaries).collect()
+    }
+
+    /// parse_with_classifier returns splitted string slice from input, looking up features with the
+    /// keys given by classifier instead of the characters themselves.
+    ///
+    /// classifier returns the key of a character, or `None` to use the character itself. Keys of
+    /// adjacent characters are concatenated for bigram and trigram features. This is a hook for
+    /// domain adaptation without retraining, e.g. treating private use characters like kanji.
+    /// Keys which do not resemble the text the model was trained on degrade the quality of the
+    /// result.
+    ///
+    /// * `input` - input sentences.
+    /// * `classifier` - function returning the feature key of a character.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// // treat a private use character as "油".
+    /// let words = model.parse_with_classifier("水と\u{e000}", |c| (c == '\u{e000}').then_some("油"));
+    ///
+    /// assert_eq!(words, vec!["水と", "\u{e000}"]);
+    /// ```
+    pub fn parse_with_classifier<'i, 'k, F>(&self, input: &'i str, classifier: F) -> Vec<&'i str>
+    where
+        F: Fn(char) -> Option<&'k str>,
+    {
+        let mut text = String::with_capacity(input.len());
+        let mut ranges = Vec::new();
+        for c in input.chars() {
+            let start = text.len();
+            match classifier(c) {
+                Some(key) => text.push_str(key),
+                None => text.push(c),
+            }
+            ranges.push(start..text.len());
+        }
+        let units = ranges.into_iter().map(|r| (r.start, &text[r]));
+        // the i-th score is for the boundary before the i + 1-th character of input.
+        let boundaries = self
+            .unit_score_iter(&text, units, FeatureMask::ALL)
+            .zip(input.char_indices().skip(1))
+            .filter(|&((_, score), _)| score >= DEFAULT_THRESHOLD)
+            .map(|(_, (offset, _))| offset);
+        split_chunks(input, boundaries).collect()
+    }
+
+    /// insert_breaks returns input with `sep` inserted at every boundary.
+    ///
+    /// * `input` - input sentences.
+    /// * `sep` - separator, e.g. zero width space (`"\u{200B}"`), `"<wbr>"` or soft hyphen (`"\u{00AD}"`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let text = model.insert_breaks("これはテストです。", "\u{200B}");
+    ///
+    /// assert_eq!(text, "これは\u{200B}テストです。");
+    /// ```
+    pub fn insert_breaks(&self, input: &str, sep: &str) -> String {
+        let mut out = String::with_capacity(input.len());
+        let mut start = 0;
+        for end in self.boundary_iter(input, DEFAULT_THRESHOLD) {
+            out.push_str(&input[start..end]);
+            out.push_str(sep);
+            start = end;
+        }
+        out.push_str(&input[start..]);
+        out
+    }
+
+    /// join returns the chunks of input joined with `delimiter`, e.g. `これは/テストです。`.
+    ///
+    /// The result is the same as `parse(input).join(delimiter)`, but it is written directly
+    /// into the output without collecting the chunks.
+    ///
+    /// * `input` - input sentences.
+    /// * `delimiter` - delimiter between chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let text = model.join("これはテストです。", "/");
+    ///
+    /// assert_eq!(text, "これは/テストです。");
+    /// ```
+    pub fn join(&self, input: &str, delimiter: &str) -> String {
+        self.insert_breaks(input, delimiter)
+    }
+
+    /// parse_visualized returns input with a visible separator between chunks, for debugging.
+    ///
+    /// * `input` - input sentences.
+    /// * `sep` - separator, defaults to `DEFAULT_VISUALIZE_SEPARATOR` (`"|"`) if `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    ///
+    /// assert_eq!(model.parse_visualized("これはテストです。", None), "これは|テストです。");
+    /// assert_eq!(model.parse_visualized("これはテストです。", Some(" / ")), "これは / テストです。");
+    /// ```
+    pub fn parse_visualized(&self, input: &str, sep: Option<&str>) -> String {
+        self.insert_breaks(input, sep.unwrap_or(DEFAULT_VISUALIZE_SEPARATOR))
+    }
+
+    /// wrap returns lines assembled from splitted chunks so that each line fits in `max_width`.
+    ///
+    /// Chunks are accumulated greedily, and a new line is started when adding the next chunk would exceed `max_width`.
+    /// A chunk wider than `max_width` is emitted on its own line.
+    ///
+    /// * `input` - input sentences.
+    /// * `max_width` - maximum width of a line.
+    /// * `measure` - returns the width of a chunk, e.g. counting east asian wide characters as 2 columns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let lines = model.wrap("今日はとても天気です。", 10, |s| s.chars().count() * 2);
+    ///
+    /// assert_eq!(lines, vec!["今日は", "とても", "天気です。"]);
+    /// ```
+    pub fn wrap(
+        &self,
+        input: &str,
+        max_width: usize,
+        measure: impl Fn(&str) -> usize,
+    ) -> Vec<String> {
+        let mut lines = vec![];
+        let mut line = String::new();
+        let mut width = 0;
+        for chunk in self.parse_iter(input) {
+            let chunk_width = measure(chunk);
+            if !line.is_empty() && width + chunk_width > max_width {
+                lines.push(core::mem::take(&mut line));
+                width = 0;
+            }
+            line.push_str(chunk);
+            width += chunk_width;
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// wrap_with_hyphen is like [`Model::wrap`], but appends `hyphen` to every line which is wrapped
+    /// at a break, i.e. every line but the last.
+    ///
+    /// The width of `hyphen` is reserved when deciding whether a chunk fits, so that each line fits
+    /// in `max_width` including the hyphen. Use `"\u{AD}"` (soft hyphen) to keep the output
+    /// rewrappable, or `"-"` for a hyphen always shown in print.
+    ///
+    /// * `input` - input sentences.
+    /// * `max_width` - maximum width of a line, including the hyphen.
+    /// * `hyphen` - string appended to wrapped lines.
+    /// * `measure` - returns the width of a chunk or the hyphen.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let lines = model.wrap_with_hyphen("今日はとても天気です。", 6, "-", |s| s.chars().count());
+    ///
+    /// assert_eq!(lines, vec!["今日は-", "とても-", "天気です。"]);
+    /// ```
+    pub fn wrap_with_hyphen(
+        &self,
+        input: &str,
+        max_width: usize,
+        hyphen: &str,
+        measure: impl Fn(&str) -> usize,
+    ) -> Vec<String> {
+        let hyphen_width = measure(hyphen);
+        let mut lines = vec![];
+        let mut line = String::new();
+        let mut width = 0;
+        let mut chunks = self.parse_iter(input).peekable();
+        while let Some(chunk) = chunks.next() {
+            // a hyphen is needed after this chunk if the line is wrapped right after it.
+            let chunk_width = measure(chunk);
+            let reserved = if chunks.peek().is_some() {
+                hyphen_width
+            } else {
+                0
+            };
+            if !line.is_empty() && width + chunk_width + reserved > max_width {
+                line.push_str(hyphen);
+                lines.push(core::mem::take(&mut line));
+                width = 0;
+            }
+            line.push_str(chunk);
+            width += chunk_width;
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// parse_columns returns splitted string slice from input so that no chunk exceeds `max_cols`
+    /// display columns, counting east asian wide characters as 2 columns.
+    ///
+    /// A chunk wider than `max_cols` is split at the last character which fits, even if the model
+    /// does not break there. A single character wider than `max_cols` is a chunk on its own, and
+    /// zero width characters such as combining marks are kept with the preceding character.
+    ///
+    /// * `input` - input sentences.
+    /// * `max_cols` - maximum display columns of a chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let words = model.parse_columns("これはテストです。", 8);
+    ///
+    /// assert_eq!(words, vec!["これは", "テストで", "す。"]);
+    /// ```
+    #[cfg(feature = "unicode-width")]
+    pub fn parse_columns<'i>(&self, input: &'i str, max_cols: usize) -> Vec<&'i str> {
+        use unicode_width::UnicodeWidthChar;
+
+        let mut chunks = vec![];
+        for chunk in self.parse(input) {
+            let mut start = 0;
+            let mut cols = 0;
+            for (i, c) in chunk.char_indices() {
+                let width = c.width().unwrap_or(0);
+                if i > start && cols + width > max_cols {
+                    chunks.push(&chunk[start..i]);
+                    start = i;
+                    cols = 0;
+                }
+                cols += width;
+            }
+            chunks.push(&chunk[start..]);
+        }
+        chunks
+    }
+
+    /// scores returns the score of every boundary between two characters of input.
+    ///
+    /// The `i`-th score is for the boundary before the `i + 1`-th character, and a break is inserted there
+    /// when the score reaches the threshold.
+    ///
+    /// * `input` - input sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let scores = model.scores("水と油");
+    ///
+    /// assert_eq!(scores.len(), 2);
+    /// assert!(scores[0] < budoux::DEFAULT_THRESHOLD);
+    /// assert!(scores[1] >= budoux::DEFAULT_THRESHOLD);
+    /// ```
+    pub fn scores(&self, input: &str) -> Vec<i32> {
+        let mut scores = vec![0; input.chars().count().saturating_sub(1)];
+        self.fill_scores(input, &mut scores);
+        scores
+    }
+
+    /// break_probabilities returns the probability of a break at every boundary of input, in the
+    /// same order as `scores`.
+    ///
+    /// The bundled models are trained with AdaBoost and their feature scores are the weights scaled
+    /// by 1000. A probability is `1 / (1 + exp(-score / scale))` where scale is
+    /// `500 * score_multiplier`, i.e. 1000 by default, which is the logistic interpretation of the
+    /// AdaBoost margin. A boundary is a break with `DEFAULT_THRESHOLD` if and only if its probability
+    /// is above 0.5. Requires the `std` feature.
+    ///
+    /// * `input` - input sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let probabilities = model.break_probabilities("水と油");
+    ///
+    /// assert_eq!(probabilities.len(), 2);
+    /// assert!(probabilities[0] < 0.5);
+    /// assert!(probabilities[1] > 0.5);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn break_probabilities(&self, input: &str) -> Vec<f32> {
+        let scale = 500.0 * f64::from(self.score_multiplier);
+        self.score_iter(input)
+            .map(|(_, score)| (1.0 / (1.0 + (-f64::from(score) / scale).exp())) as f32)
+            .collect()
+    }
+
+    /// fill_scores writes the score of every boundary of input into `scores` in one pass, and
+    /// returns the number of scores written.
+    ///
+    /// The `i`-th score is for the boundary before the `i + 1`-th character like `scores`. Scores
+    /// which do not fit in `scores` are not computed, so a buffer of `input.chars().count() - 1`
+    /// elements receives every score.
+    ///
+    /// * `input` - input sentences.
+    /// * `scores` - output buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let mut scores = [0; 8];
+    ///
+    /// assert_eq!(model.fill_scores("水と油", &mut scores), 2);
+    /// assert_eq!(scores[..2], model.scores("水と油"));
+    /// ```
+    pub fn fill_scores(&self, input: &str, scores: &mut [i32]) -> usize {
+        let mut len = 0;
+        for (slot, (_, score)) in scores.iter_mut().zip(self.score_iter(input)) {
+            *slot = score;
+            len += 1;
+        }
+        len
+    }
+
+    /// score_at returns the score of the boundary between the characters at `char_index - 1` and
+    /// `char_index` of input, which is the score `parse` compares with the threshold.
+    ///
+    /// Only the characters around the boundary are scored. Out of range indices return `None`.
+    ///
+    /// * `input` - input sentences.
+    /// * `char_index` - index of the character after the boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    ///
+    /// assert_eq!(model.score_at("水と油", 2), Some(model.scores("水と油")[1]));
+    /// assert_eq!(model.score_at("水と油", 0), None);
+    /// assert_eq!(model.score_at("水と油", 3), None);
+    /// ```
+    pub fn score_at(&self, input: &str, char_index: usize) -> Option<i32> {
+        if char_index == 0 {
+            return None;
+        }
+        let first = char_index.saturating_sub(3);
+        let mut window = [(0, '\0'); 6];
+        let mut len = 0;
+        for c in input
+            .char_indices()
+            .skip(first)
+            .take(char_index - first + 3)
+        {
+            window[len] = c;
+            len += 1;
+        }
+        let i = char_index - first;
+        (i < len).then(|| self.score(input, &window[..len], i))
+    }
+
+    /// parse_with_scores returns splitted string slice from input paired with the score of the boundary
+    /// before each chunk.
+    ///
+    /// The first chunk has no boundary before it, so its score is `None`.
+    ///
+    /// * `input` - input sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let words = model.parse_with_scores("水と油");
+    ///
+    /// assert_eq!(words[0], ("水と", None));
+    /// assert_eq!(words[1].0, "油");
+    /// assert!(words[1].1.unwrap() >= budoux::DEFAULT_THRESHOLD);
+    /// ```
+    pub fn parse_with_scores<'i>(&self, input: &'i str) -> Vec<(&'i str, Option<i32>)> {
+        let mut chunks = vec![];
+        let mut start = 0;
+        let mut start_score = None;
+        for (offset, score) in self.score_iter(input) {
+            if score >= DEFAULT_THRESHOLD {
+                chunks.push((&input[start..offset], start_score));
+                start = offset;
+                start_score = Some(score);
+            }
+        }
+        if !input.is_empty() {
+            chunks.push((&input[start..], start_score));
+        }
+        chunks
+    }
+
+    /// count_boundaries returns the number of boundaries in input without allocation.
+    ///
+    /// * `input` - input sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    ///
+    /// assert_eq!(model.count_boundaries("これはテストです。"), 1);
+    /// assert_eq!(model.count_boundaries("日本語"), 0);
+    /// ```
+    pub fn count_boundaries(&self, input: &str) -> usize {
+        self.boundary_iter(input, DEFAULT_THRESHOLD).count()
+    }
+
+    /// has_break_between returns whether there is a boundary between the characters at `char_i - 1`
+    /// and `char_i` of input.
+    ///
+    /// Only the characters around the boundary are scored, so this is cheaper than `parse` when
+    /// a single decision is needed. Out of range indices return `false`.
+    ///
+    /// * `input` - input sentences.
+    /// * `char_i` - index of the character after the boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    ///
+    /// assert!(model.has_break_between("水と油", 2));
+    /// assert!(!model.has_break_between("水と油", 1));
+    /// assert!(!model.has_break_between("水と油", 3));
+    /// ```
+    pub fn has_break_between(&self, input: &str, char_i: usize) -> bool {
+        self.score_at(input, char_i)
+            .is_some_and(|score| score >= DEFAULT_THRESHOLD)
+    }
+
+    /// nearest_break returns the byte offset of the boundary closest to `target_byte`, or `None` if
+    /// input has no boundary.
+    ///
+    /// Ties resolve toward the earlier boundary. Boundaries are scored from the start of input and
+    /// scoring stops at the first boundary after `target_byte`.
+    ///
+    /// * `input` - input sentences.
+    /// * `target_byte` - byte offset the boundary should be close to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let input = "今日はとても天気です。";
+    ///
+    /// assert_eq!(model.parse_boundaries(input), vec![9, 18]);
+    /// assert_eq!(model.nearest_break(input, 12), Some(9));
+    /// assert_eq!(model.nearest_break(input, 15), Some(18));
+    /// assert_eq!(model.nearest_break("日本語", 3), None);
+    /// ```
+    pub fn nearest_break(&self, input: &str, target_byte: usize) -> Option<usize> {
+        let mut before = None;
+        for boundary in self.boundary_iter(input, DEFAULT_THRESHOLD) {
+            if boundary <= target_byte {
+                before = Some(boundary);
+                continue;
+            }
+            // every later boundary is farther than this one.
+            return match before {
+                Some(before) if target_byte - before <= boundary - target_byte => Some(before),
+                _ => Some(boundary),
+            };
+        }
+        before
+    }
+
+    /// parse_batch returns splitted string slice from each input.
+    ///
+    /// With the `rayon` feature, inputs are parsed in parallel.
+    ///
+    /// * `inputs` - input sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let words = model.parse_batch(&["これはテストです。", "水と油"]);
+    ///
+    /// assert_eq!(words, vec![vec!["これは", "テストです。"], vec!["水と", "油"]]);
+    /// ```
+    pub fn parse_batch<'i>(&self, inputs: &[&'i str]) -> Vec<Vec<&'i str>> {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            inputs.par_iter().map(|input| self.parse(input)).collect()
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            inputs.iter().map(|input| self.parse(input)).collect()
+        }
+    }
+
+    fn boundaries_with_threshold(&self, input: &str, threshold: i32) -> Vec<usize> {
+        self.boundary_iter(input, threshold).collect()
+    }
+
+    /// boundary_iter lazily yields the byte offsets of boundaries whose score reaches `threshold`.
+    fn boundary_iter<'a>(
+        &'a self,
+        input: &'a str,
+        threshold: i32,
+    ) -> impl Iterator<Item = usize> + 'a {
+        // the fast path never polls score_iter.
+        let limit = if self.never_breaks_ascii(input, threshold) {
+            0
+        } else {
+            usize::MAX
+        };
+        self.score_iter(input)
+            .take(limit)
+            .filter(move |&(_, score)| score >= threshold)
+            .map(|(offset, _)| offset)
+    }
+
+    /// score_iter lazily yields the byte offset and score of every boundary without allocation.
+    fn score_iter<'a>(&'a self, input: &'a str) -> impl Iterator<Item = (usize, i32)> + 'a {
+        self.unit_score_iter(input, input.char_indices(), FeatureMask::ALL)
+    }
+
+    /// unit_score_iter lazily yields the byte offset and score of every boundary between units.
+    ///
+    /// A score only depends on the 3 units before and after the boundary, so a window of
+    /// 6 units is enough.
+    fn unit_score_iter<'a, U: Unit + 'a>(
+        &'a self,
+        input: &'a str,
+        mut units: impl Iterator<Item = U> + 'a,
+        mask: FeatureMask,
+    ) -> impl Iterator<Item = (usize, i32)> + 'a {
+        let mut window = [U::default(); 6];
+        let mut len = 0;
+        // i is the index in window of the unit after the boundary.
+        let mut i = 1;
+        core::iter::from_fn(move || {
+            while len < i + 3 {
+                match units.next() {
+                    Some(c) => {
+                        window[len] = c;
+                        len += 1;
+                    }
+                    None => break,
+                }
+            }
+            if i >= len {
+                return None;
+            }
+            let item = (
+                window[i].range().start,
+                self.masked_score(input, &window[..len], i, mask),
+            );
+            if i == 3 {
+                window.copy_within(1..len, 0);
+                len -= 1;
+            } else {
+                i += 1;
+            }
+            Some(item)
+        })
+    }
+
+    /// score returns the score of the boundary between `chars[i - 1]` and `chars[i]`.
+    fn score<U: Unit>(&self, input: &str, chars: &[U], i: usize) -> i32 {
+        self.masked_score(input, chars, i, FeatureMask::ALL)
+    }
+
+    /// masked_score is like `score`, but only the feature groups in mask contribute to the score.
+    fn masked_score<U: Unit>(&self, input: &str, chars: &[U], i: usize, mask: FeatureMask) -> i32 {
+        let features = self
+            .feature_scores(input, chars, i, mask)
+            .map(|(_, score)| score);
+        saturate(self.base_score + features.sum::<i64>())
+    }
+
+    /// feature_scores yields the score each feature group contributes to the boundary between
+    /// `chars[i - 1]` and `chars[i]`, for the groups in mask which fit in chars.
+    fn feature_scores<'a, U: Unit>(
+        &'a self,
+        input: &'a str,
+        chars: &'a [U],
+        i: usize,
+        mask: FeatureMask,
+    ) -> impl Iterator<Item = (&'static str, i64)> + 'a {
+        let multiplier = i64::from(self.score_multiplier);
+        FEATURE_GROUPS
+            .iter()
+            .enumerate()
+            .filter(move |&(index, _)| mask.contains(FeatureMask::from_index(index)))
+            .filter_map(move |(_, &(group, offset, len))| {
+                let start = i.checked_add_signed(offset)?;
+                let end = start + len;
+                if end > chars.len() {
+                    return None;
+                }
+                let key = &input[chars[start].range().start..chars[end - 1].range().end];
+                let score = self.get(group).and_then(|v| v.get(key)).copied();
+                Some((group, i64::from(score.unwrap_or(0)) * multiplier))
+            })
+    }
+}
+
+/// round_score rounds a floating point score to the nearest `i32`, rounding half away from zero.
+///
+/// The cast truncates toward zero and saturates at the bounds of `i32`. `f64::round` is not
+/// available without `std`.
+fn round_score(score: f64) -> i32 {
+    (if score < 0.0 {
+        score - 0.5
+    } else {
+        score + 0.5
+    }) as i32
+}
+
+/// saturate converts an accumulated score to `i32`, clamping it to the range of `i32`.
+fn saturate(score: i64) -> i32 {
+    score.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32
+}
+
+/// Unit is a unit of scoring, e.g. a character or a grapheme cluster, with its byte offset.
+trait Unit: Copy + Default {
+    /// range returns the byte range of the unit.
+    fn range(self) -> Range<usize>;
+}
+
+impl Unit for (usize, char) {
+    fn range(self) -> Range<usize> {
+        let (start, c) = self;
+        start..start + c.len_utf8()
+    }
+}
+
+impl Unit for (usize, &str) {
+    fn range(self) -> Range<usize> {
+        let (start, s) = self;
+        start..start + s.len()
+    }
+}
+
+/// split_chunks lazily splits input at the byte offsets of boundaries.
+fn split_chunks(
+    input: &str,
+    mut boundaries: impl Iterator<Item = usize>,
+) -> impl Iterator<Item = &str> {
+    let mut start = if input.is_empty() { None } else { Some(0) };
+    core::iter::from_fn(move || {
+        let s = start?;
+        match boundaries.next() {
+            Some(end) => {
+                start = Some(end);
+                Some(&input[s..end])
+            }
+            None => {
+                start = None;
+                Some(&input[s..])
+            }
+        }
+    })
+}
+
+/// try_split_chunks splits input at the byte offsets of boundaries, checking that every boundary is
+/// in order and on a `char` boundary of input.
+fn try_split_chunks(
+    input: &str,
+    boundaries: impl Iterator<Item = usize>,
+) -> Result<Vec<&str>, ParseError> {
+    let mut chunks = vec![];
+    let mut start = 0;
+    for end in boundaries {
+        let chunk = input
+            .get(start..end)
+            .filter(|chunk| !chunk.is_empty())
+            .ok_or(ParseError::InvalidBoundary(end))?;
+        chunks.push(chunk);
+        start = end;
+    }
+    if !input.is_empty() {
+        let chunk = input
+            .get(start..)
+            .filter(|chunk| !chunk.is_empty())
+            .ok_or(ParseError::InvalidBoundary(start))?;
+        chunks.push(chunk);
+    }
+    Ok(chunks)
+}
+
+impl core::str::FromStr for Model {
+    type Err = Error;
+
+    /// Deserializes the model from BudouX model json.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model: budoux::Model = r#"{"UW4": {"油": 1000}}"#.parse().unwrap();
+    ///
+    /// assert_eq!(model.parse("水と油"), vec!["水と", "油"]);
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Model::from_str(s)
+    }
+}
+
+/// Hashes the features sorted by group and key, so that equal models have equal hashes
+/// regardless of the map order.
+impl core::hash::Hash for Model {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.sorted_features().hash(state);
+    }
+}
+
+impl AsRef<Model> for Model {
+    fn as_ref(&self) -> &Model {
+        self
+    }
+}
+
+/// parse_with returns splitted string slice from input using any reference to a model.
+///
+/// Both `&Model` and `&'static Model` returned by the `models` functions are accepted,
+/// as well as any other type implementing `AsRef<Model>`.
+///
+/// * `model` - model to parse with.
+/// * `input` - input sentences.
+///
+/// # Examples
+///
+/// ```rust
+/// fn first_chunk<M: AsRef<budoux::Model>>(model: M, input: &str) -> Option<&str> {
+///     budoux::parse_with(model, input).first().copied()
+/// }
+///
+/// let owned = budoux::Model::from_str(r#"{"UW4": {"油": 1000}}"#).unwrap();
+///
+/// assert_eq!(first_chunk(budoux::models::default_japanese_model(), "水と油"), Some("水と"));
+/// assert_eq!(first_chunk(&owned, "水と油"), Some("水と"));
+/// ```
+pub fn parse_with<M: AsRef<Model>>(model: M, input: &str) -> Vec<&str> {
+    model.as_ref().parse(input)
+}
+
+/// merge_chunks returns chunks with adjacent ones merged where `should_merge` says so.
+///
+/// Chunks are walked from the start, and `should_merge` is called with the chunk merged so far
+/// and the next chunk, so that a compound spanning more than two chunks can be merged step by step.
+///
+/// * `chunks` - chunks to merge, e.g. the result of `Model::parse`.
+/// * `should_merge` - predicate deciding whether the two chunks are merged.
+///
+/// # Examples
+///
+/// ```rust
+/// let model = budoux::models::default_japanese_model();
+/// let chunks = model.parse("これはテストです。今日は晴天です。");
+/// let sentences = budoux::merge_chunks(&chunks, |a, _| !a.ends_with('。'));
+///
+/// assert_eq!(chunks, vec!["これは", "テストです。", "今日は", "晴天です。"]);
+/// assert_eq!(sentences, vec!["これはテストです。", "今日は晴天です。"]);
+/// ```
+pub fn merge_chunks(chunks: &[&str], should_merge: impl Fn(&str, &str) -> bool) -> Vec<String> {
+    let mut merged: Vec<String> = Vec::with_capacity(chunks.len());
+    for &chunk in chunks {
+        match merged.last_mut() {
+            Some(last) if should_merge(last, chunk) => last.push_str(chunk),
+            _ => merged.push(String::from(chunk)),
+        }
+    }
+    merged
+}
+
+impl serde::Serialize for Model {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.map.serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Model {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self::new(HashMap::deserialize(deserializer)?))
+    }
+}
+
+/// Creates a new `Model` instance from `(group, key, score)` triples and calculates the base score.
+/// Scores of duplicated features are summed.
+///
+/// # Examples
+///
+/// ```rust
+/// let model = [
+///     ("UW4".to_string(), "油".to_string(), 600),
+///     ("UW4".to_string(), "油".to_string(), 400),
+/// ]
+/// .into_iter()
+/// .collect::<budoux::Model>();
+///
+/// assert_eq!(model.as_inner()["UW4"]["油"], 1000);
+/// assert_eq!(model.parse("水と油"), vec!["水と", "油"]);
+/// ```
+impl FromIterator<(String, String, i32)> for Model {
+    fn from_iter<T: IntoIterator<Item = (String, String, i32)>>(iter: T) -> Self {
+        let mut map = HashMap::<String, HashMap<String, i32>>::new();
+        for (group, key, score) in iter {
+            let feature = map.entry(group).or_default().entry(key).or_default();
+            *feature = feature.saturating_add(score);
+        }
+        Self::new(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec, vec::Vec};
+
+    #[test]
+    fn test_parse() {
+        let m = super::models::default_japanese_model();
+
+        assert_eq!(m.parse(""), Vec::<&str>::new());
+        assert_eq!(m.parse("日本語"), vec!["日本語"]);
+        assert_eq!(m.parse("水と油"), vec!["水と", "油"]);
+        assert_eq!(
+            m.parse("水道水とミネラルウォーター"),
+            vec!["水道水と", "ミネラルウォーター"]
+        );
+        assert_eq!(
+            m.parse("PythonとJavaScriptとGolang"),
+            vec!["Pythonと", "JavaScriptと", "Golang"]
+        );
+        assert_eq!(
+            m.parse("日本語の文章において語の区切りに空白を挟んで記述すること"),
+            vec![
+                "日本語の",
+                "文章に",
+                "おいて",
+                "語の",
+                "区切りに",
+                "空白を",
+                "挟んで",
+                "記述する",
+                "こと"
+            ]
+        );
+        assert_eq!(
+            m.parse("これはテストです。"),
+            vec!["これは", "テストです。"]
+        );
+        assert_eq!(
+            m.parse("これは美しいペンです。"),
+            vec!["これは", "美しい", "ペンです。"]
+        );
+        assert_eq!(m.parse("今日は天気です。"), vec!["今日は", "天気です。"]);
+        assert_eq!(
+            m.parse("今日はとても天気です。"),
+            vec!["今日は", "とても", "天気です。"]
+        );
+        assert_eq!(
+            m.parse("あなたに寄り添う最先端のテクノロジー。"),
+            vec!["あなたに", "寄り添う", "最先端の", "テクノロジー。"]
+        );
+        assert_eq!(
+            m.parse("これはテストです。今日は晴天です。"),
+            vec!["これは", "テストです。", "今日は", "晴天です。"]
+        );
+        assert_eq!(
+            m.parse("これはテストです。\n今日は晴天です。"),
+            vec!["これは", "テストです。", "\n今日は", "晴天です。"]
+        );
+    }
+
+    #[test]
+    fn test_average_chunk_len() {
+        let m = super::models::default_japanese_model();
+
+        assert_eq!(m.average_chunk_len(&[], super::DEFAULT_THRESHOLD), 0.0);
+        assert_eq!(m.average_chunk_len(&[""], super::DEFAULT_THRESHOLD), 0.0);
+        // "これは", "テストです。", "水と", "油"
+        assert_eq!(
+            m.average_chunk_len(
+                &["これはテストです。", "", "水と油"],
+                super::DEFAULT_THRESHOLD
+            ),
+            3.0
+        );
+        assert!(
+            m.average_chunk_len(&["これはテストです。"], 5000)
+                >= m.average_chunk_len(&["これはテストです。"], -5000)
+        );
+    }
+
+    #[test]
+    fn test_parse_owned() {
+        let m = super::models::default_japanese_model();
+        let words = {
+            let input = String::from("これはテストです。今日は晴天です。");
+            m.parse_owned(&input)
+        };
+
+        assert_eq!(
+            words,
+            vec!["これは", "テストです。", "今日は", "晴天です。"]
+        );
+        assert_eq!(m.parse_owned(""), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_parse_with_threshold() {
+        let m = super::models::default_japanese_model();
+        let input = "日本語の文章において語の区切りに空白を挟んで記述すること";
+
+        assert_eq!(
+            m.parse_with_threshold(input, super::DEFAULT_THRESHOLD),
+            m.parse(input)
+        );
+        assert_eq!(m.parse_with_threshold(input, i32::MAX), vec![input]);
+        assert_eq!(
+            m.parse_with_threshold(input, i32::MIN).len(),
+            input.chars().count()
+        );
+        assert_eq!(m.parse_with_threshold("", i32::MIN), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_parse_with_options() {
+        use super::ParseOptions;
+
+        let m = super::models::default_japanese_model();
+        let options = ParseOptions {
+            hard_break_on_newline: true,
+            ..Default::default()
+        };
+
+        assert_eq!(m.parse_with_options("", &options), Vec::<&str>::new());
+        assert_eq!(
+            m.parse_with_options("日本語", &ParseOptions::default()),
+            vec!["日本語"]
+        );
+        assert_eq!(
+            m.parse_with_options("これはテストです。\n今日は晴天です。", &options),
+            vec!["これは", "テストです。\n", "今日は", "晴天です。"]
+        );
+        assert_eq!(
+            m.parse_with_options("日本語\r\n日本語\n\n日本語\r", &options),
+            vec!["日本語\r\n", "日本語\n\n", "日本語\r"]
+        );
+        assert_eq!(
+            m.parse_with_options("\n日本語", &options),
+            vec!["\n", "日本語"]
+        );
+    }
+
+    #[test]
+    fn test_parse_blank() {
+        use super::ParseOptions;
+
+        let m = super::models::default_japanese_model();
+        let options = ParseOptions {
+            skip_blank: true,
+            ..Default::default()
+        };
+
+        for input in ["\n", "   ", " \t\r\n ", "\u{3000}\u{3000}", "\u{1}\u{7f}"] {
+            assert_eq!(m.parse(input), vec![input]);
+            assert_eq!(m.parse_with_options(input, &options), Vec::<&str>::new());
+        }
+        assert_eq!(m.parse_with_options("", &options), Vec::<&str>::new());
+        assert_eq!(
+            m.parse_with_options(" 水と油\n", &options),
+            m.parse(" 水と油\n")
+        );
+    }
+
+    #[test]
+    fn test_parse_with_options_punctuation() {
+        use super::ParseOptions;
+
+        let m = super::models::default_japanese_model();
+        let detach = ParseOptions {
+            detach_punctuation: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            m.parse_with_options("これはテストです。今日は、晴天です。", &detach),
+            vec![
+                "これは",
+                "テストです",
+                "。",
+                "今日は",
+                "、",
+                "晴天です",
+                "。"
+            ]
+        );
+        assert_eq!(m.parse_with_options("。", &detach), vec!["。"]);
+        let custom = ParseOptions {
+            punctuation: "!".into(),
+            ..detach
+        };
+        assert_eq!(
+            m.parse_with_options("日本語!日本語。", &custom),
+            vec!["日本語", "!日本語。"]
+        );
+
+        // a model which breaks before every closing bracket.
+        let m =
+            super::Model::from_str(r#"{"UW1": {"\u0000": -1000}, "UW4": {"」": 1000, ")": 1000}}"#)
+                .unwrap();
+        let closing = ParseOptions {
+            no_break_before_closing: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            m.parse("「日本語」(日本語)"),
+            vec!["「日本語", "」(日本語", ")"]
+        );
+        assert_eq!(
+            m.parse_with_options("「日本語」(日本語)", &closing),
+            vec!["「日本語」(日本語)"]
+        );
+        let both = ParseOptions {
+            punctuation: "」".into(),
+            detach_punctuation: true,
+            ..closing
+        };
+        assert_eq!(
+            m.parse_with_options("「日本語」", &both),
+            vec!["「日本語」"]
+        );
+    }
+
+    #[test]
+    fn test_parse_with_options_kinsoku() {
+        use super::ParseOptions;
+
+        // a model which breaks before and after every bracket and before small kana.
+        let m = super::Model::from_str(
+            r#"{
+                "UW1": {"\u0000": -4000},
+                "UW3": {"「": 1000, "」": 1000},
+                "UW4": {"「": 1000, "」": 1000, "ょ": 1000}
+            }"#,
+        )
+        .unwrap();
+        let options = ParseOptions {
+            kinsoku: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            m.parse("日本「きょう」日本"),
+            vec!["日本", "「", "き", "ょう", "」", "日本"]
+        );
+        assert_eq!(
+            m.parse_with_options("日本「きょう」日本", &options),
+            vec!["日本", "「きょう」", "日本"]
+        );
+        assert_eq!(m.parse_with_options("", &options), Vec::<&str>::new());
+        assert_eq!(m.parse_with_options("」「", &options), vec!["」", "「"]);
+
+        // forced breaks are kept.
+        let capped = ParseOptions {
+            max_chunk_chars: Some(2),
+            ..options.clone()
+        };
+        for word in m.parse_with_options("日本「きょう」日本", &capped) {
+            assert!(word.chars().count() <= 2);
+        }
+        let newline = ParseOptions {
+            hard_break_on_newline: true,
+            ..options.clone()
+        };
+        assert_eq!(
+            m.parse_with_options("日本「\n」日本", &newline),
+            vec!["日本", "「\n", "」", "日本"]
+        );
+        let custom = ParseOptions {
+            kinsoku_not_at_start: "".into(),
+            kinsoku_not_at_end: "".into(),
+            ..options
+        };
+        assert_eq!(
+            m.parse_with_options("日本「きょう」日本", &custom),
+            m.parse("日本「きょう」日本")
+        );
+    }
+
+    #[test]
+    fn test_parse_with_options_split_on_whitespace() {
+        use super::ParseOptions;
+
+        let m = super::models::default_japanese_model();
+        let options = ParseOptions {
+            split_on_whitespace: true,
+            ..Default::default()
+        };
+
+        assert_eq!(m.parse_with_options("", &options), Vec::<&str>::new());
+        assert_eq!(m.parse_with_options(" \t ", &options), Vec::<&str>::new());
+        assert_eq!(
+            m.parse_with_options("これは  テストです。\tBudouX is fine ", &options),
+            vec!["これは", "テストです。", "BudouX", "is", "fine"]
+        );
+        assert_eq!(
+            m.parse_with_options(" 日本語\n\n日本語", &options),
+            vec!["日本語", "日本語"]
+        );
+    }
+
+    #[test]
+    fn test_parse_with_options_keep_latin_words_whole() {
+        use super::ParseOptions;
+
+        let m = super::models::default_japanese_model();
+        let options = ParseOptions {
+            keep_latin_words_whole: true,
+            ..Default::default()
+        };
+        let input = "PythonとJavaScriptとHTML5";
+
+        assert_eq!(m.parse_with_options("", &options), Vec::<&str>::new());
+        assert_eq!(m.parse_with_options(input, &options), m.parse(input));
+
+        // every boundary scores at least the threshold, but latin words are still kept.
+        let everywhere = ParseOptions {
+            threshold: i32::MIN,
+            ..options.clone()
+        };
+        assert_eq!(
+            m.parse_with_options(input, &everywhere),
+            vec!["Python", "と", "JavaScript", "と", "HTML5"]
+        );
+        assert_eq!(
+            m.parse_with_options("Wi-Fi", &everywhere),
+            vec!["Wi", "-", "Fi"]
+        );
+
+        let capped = ParseOptions {
+            max_chunk_chars: Some(4),
+            ..options
+        };
+        assert_eq!(
+            m.parse_with_options("JavaScript", &capped),
+            vec!["Java", "Scri", "pt"]
+        );
+    }
+
+    #[test]
+    fn test_parse_with_options_preserve_indent() {
+        use super::ParseOptions;
+
+        let m = super::models::default_japanese_model();
+        let options = ParseOptions {
+            preserve_indent: true,
+            ..Default::default()
+        };
+
+        assert_eq!(m.parse_with_options("", &options), Vec::<&str>::new());
+        assert_eq!(
+            m.parse_with_options("これはテストです。", &options),
+            m.parse("これはテストです。")
+        );
+        assert_eq!(
+            m.parse_with_options("  \tこれはテストです。", &options),
+            vec!["  \tこれは", "テストです。"]
+        );
+        assert_eq!(
+            m.parse_with_options("今日は\n    これはテストです。\n  水と油", &options),
+            vec!["今日は", "\n    これは", "テストです。", "\n  水と", "油"]
+        );
+        let hard_break = ParseOptions {
+            hard_break_on_newline: true,
+            ..options.clone()
+        };
+        assert_eq!(
+            m.parse_with_options("今日は\n    これはテストです。", &hard_break),
+            vec!["今日は\n", "    これは", "テストです。"]
+        );
+        assert_eq!(m.parse_with_options("   ", &options), vec!["   "]);
+        // spaces in the middle of a line are not indent.
+        assert_eq!(
+            m.parse_with_options("これは  テスト", &options),
+            m.parse("これは  テスト")
+        );
+
+        let normalized = ParseOptions {
+            normalize: true,
+            ..options.clone()
+        };
+        assert_eq!(
+            m.parse_with_options("  これはテストです。", &normalized),
+            vec!["  これは", "テストです。"]
+        );
+    }
+
+    #[test]
+    fn test_parse_with_options_min_chunk_chars() {
+        use super::ParseOptions;
+
+        let m = super::models::default_japanese_model();
+        let options = |min| ParseOptions {
+            min_chunk_chars: Some(min),
+            ..Default::default()
+        };
+        let input = "日本語の文章において語の区切りに空白を挟んで記述すること";
+
+        assert_eq!(m.parse_with_options("", &options(3)), Vec::<&str>::new());
+        assert_eq!(m.parse_with_options(input, &options(0)), m.parse(input));
+        assert_eq!(m.parse_with_options(input, &options(1)), m.parse(input));
+        // the first chunk merges forward and the last one backward.
+        assert_eq!(m.parse_with_options("水と油", &options(3)), vec!["水と油"]);
+        assert_eq!(
+            m.parse_with_options("これはテストです。", &options(100)),
+            vec!["これはテストです。"]
+        );
+        for min in 2..8 {
+            let words = m.parse_with_options(input, &options(min));
+            assert_eq!(words.concat(), input);
+            assert!(words.iter().all(|w| w.chars().count() >= min), "{words:?}");
+        }
+
+        let capped = ParseOptions {
+            max_chunk_chars: Some(4),
+            ..options(3)
+        };
+        for word in m.parse_with_options(input, &capped) {
+            assert!(word.chars().count() <= 4);
+        }
+    }
+
+    #[test]
+    fn test_parse_with_options_max_chunk_chars() {
+        use super::ParseOptions;
+
+        let m = super::models::default_japanese_model();
+        let options = |max| ParseOptions {
+            max_chunk_chars: Some(max),
+            ..Default::default()
+        };
+
+        assert_eq!(m.parse_with_options("", &options(2)), Vec::<&str>::new());
+        assert_eq!(
+            m.parse_with_options("日本語", &options(2)),
+            vec!["日本", "語"]
+        );
+        assert_eq!(
+            m.parse_with_options("これはテストです。", &options(4)),
+            vec!["これは", "テストで", "す。"]
+        );
+        assert_eq!(
+            m.parse_with_options("日本語", &options(0)),
+            m.parse_with_options("日本語", &options(1))
+        );
+
+        let input = "あああああああああああああああああああああああああああああ";
+        for max in 1..10 {
+            let words = m.parse_with_options(input, &options(max));
+            assert_eq!(words.concat(), input);
+            assert!(words.iter().all(|w| w.chars().count() <= max));
+        }
+    }
+
+    #[test]
+    fn test_parse_with_options_max_chunk_chars_newline() {
+        use super::ParseOptions;
+
+        // a newline at the cap stays with the preceding chunk.
+        let m = super::models::default_japanese_model();
+        let options = ParseOptions {
+            hard_break_on_newline: true,
+            max_chunk_chars: Some(3),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            m.parse_with_options("日本語\n日本語", &options),
+            vec!["日本語\n", "日本語"]
+        );
+        assert_eq!(
+            m.parse_with_options("日本語\r\n日本", &options),
+            vec!["日本語\r\n", "日本"]
+        );
+        assert_eq!(
+            m.parse_with_options("日本\n語語語語", &options),
+            vec!["日本\n", "語語語", "語"]
+        );
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn test_parse_small() {
+        let m = super::models::default_japanese_model();
+
+        for input in [
+            "",
+            "日本語",
+            "これはテストです。",
+            "日本語の文章において語の区切りに空白を挟んで記述すること",
+        ] {
+            let words = m.parse_small(input);
+            assert_eq!(words.as_slice(), m.parse(input));
+            assert_eq!(words.spilled(), words.len() > 4);
+        }
+    }
+
+    #[test]
+    fn test_parse_cow() {
+        use alloc::borrow::Cow;
+
+        let m = super::models::default_japanese_model();
+
+        for input in [
+            "",
+            "日本語",
+            "これはテストです。今日は晴天です。",
+            "日本語の文章において語の区切りに空白を挟んで記述すること",
+        ] {
+            let borrowed = m.parse_cow(Cow::Borrowed(input));
+            assert!(borrowed.iter().all(|w| matches!(w, Cow::Borrowed(_))));
+            assert_eq!(borrowed, m.parse(input));
+
+            let owned = m.parse_cow(Cow::Owned(String::from(input)));
+            assert!(owned.iter().all(|w| matches!(w, Cow::Owned(_))));
+            assert_eq!(owned, m.parse(input));
+        }
+    }
+
+    #[test]
+    fn test_parse_visit() {
+        let m = super::models::default_japanese_model();
+
+        for input in [
+            "",
+            "日本語",
+            "水と油",
+            "これはテストです。今日は晴天です。",
+            "日本語の文章において語の区切りに空白を挟んで記述すること",
+        ] {
+            let mut words = vec![];
+            m.parse_visit(input, |range| words.push(&input[range]));
+            assert_eq!(words, m.parse(input));
+        }
+    }
+
+    #[test]
+    fn test_parse_boundaries() {
+        let m = super::models::default_japanese_model();
+
+        assert_eq!(m.parse_boundaries(""), Vec::<usize>::new());
+        assert_eq!(m.parse_boundaries("日本語"), Vec::<usize>::new());
+        assert_eq!(m.parse_boundaries("水と油"), vec![6]);
+
+        let input = "これはテストです。今日は晴天です。";
+        let boundaries = m.parse_boundaries(input);
+        let starts = m
+            .parse(input)
+            .iter()
+            .skip(1)
+            .map(|w| w.as_ptr() as usize - input.as_ptr() as usize)
+            .collect::<Vec<_>>();
+        assert_eq!(boundaries, starts);
+    }
+
+    #[test]
+    fn test_parse_iter() {
+        let m = super::models::default_japanese_model();
+
+        assert_eq!(m.parse_iter("").next(), None);
+        for input in [
+            "日本語",
+            "水と油",
+            "日本語の文章において語の区切りに空白を挟んで記述すること",
+            "これはテストです。\n今日は晴天です。",
+        ] {
+            assert_eq!(m.parse_iter(input).collect::<Vec<_>>(), m.parse(input));
+        }
+        assert_eq!(
+            m.parse_iter("これはテストです。今日は晴天です。")
+                .take(2)
+                .collect::<Vec<_>>(),
+            vec!["これは", "テストです。"]
+        );
+    }
+
+    #[test]
+    fn test_insert_breaks() {
+        let m = super::models::default_japanese_model();
+
+        assert_eq!(m.insert_breaks("", "\u{200B}"), "");
+        assert_eq!(m.insert_breaks("日本語", "\u{200B}"), "日本語");
+        assert_eq!(
+            m.insert_breaks("これはテストです。今日は晴天です。", "<wbr>"),
+            m.parse("これはテストです。今日は晴天です。").join("<wbr>")
+        );
+    }
+
+    #[test]
+    fn test_parse_visualized() {
+        let m = super::models::default_japanese_model();
+
+        assert_eq!(m.parse_visualized("", None), "");
+        assert_eq!(
+            m.parse_visualized("あなたに寄り添う最先端のテクノロジー。", None),
+            "あなたに|寄り添う|最先端の|テクノロジー。"
+        );
+        assert_eq!(m.parse_visualized("水と油", Some("/")), "水と/油");
+    }
+
+    #[test]
+    fn test_wrap() {
+        let m = super::models::default_japanese_model();
+        let width = |s: &str| s.chars().count();
+
+        assert_eq!(m.wrap("", 10, width), Vec::<String>::new());
+        assert_eq!(
+            m.wrap("今日はとても天気です。", 6, width),
+            vec!["今日はとても", "天気です。"]
+        );
+        assert_eq!(
+            m.wrap("今日はとても天気です。", 100, width),
+            vec!["今日はとても天気です。"]
+        );
+        // chunks wider than max_width are emitted on their own line.
+        assert_eq!(
+            m.wrap("今日はとても天気です。", 1, width),
+            vec!["今日は", "とても", "天気です。"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_with_hyphen() {
+        let m = super::models::default_japanese_model();
+        let width = |s: &str| s.chars().count();
+        let input = "今日はとても天気です。";
+
+        assert_eq!(m.wrap_with_hyphen("", 10, "-", width), Vec::<String>::new());
+        for max_width in [1, 6, 100] {
+            assert_eq!(
+                m.wrap_with_hyphen(input, max_width, "", width),
+                m.wrap(input, max_width, width)
+            );
+        }
+        // the hyphen is reserved, so "今日はとても" no longer fits in 6.
+        assert_eq!(
+            m.wrap_with_hyphen(input, 6, "\u{AD}", width),
+            vec!["今日は\u{AD}", "とても\u{AD}", "天気です。"]
+        );
+        // the last line does not need a hyphen.
+        assert_eq!(
+            m.wrap_with_hyphen(input, 7, "-", width),
+            vec!["今日はとても-", "天気です。"]
+        );
+        assert_eq!(
+            m.wrap_with_hyphen(input, 100, "-", width),
+            vec!["今日はとても天気です。"]
+        );
+    }
+
+    #[test]
+    fn test_parse_ascii_fast_path() {
+        use super::models::*;
+
+        let latin = default_latin_model();
+        let ja = default_japanese_model();
+        assert!(ja.never_breaks_ascii("Hello, world!", super::DEFAULT_THRESHOLD));
+        assert!(!ja.never_breaks_ascii("Hello, 世界!", super::DEFAULT_THRESHOLD));
+        assert!(!latin.never_breaks_ascii("Hello, world!", super::DEFAULT_THRESHOLD));
+
+        // the fast path gives the same result as scoring every boundary.
+        let models = [
+            ja,
+            default_thai_model(),
+            default_simplified_chinese_model(),
+            default_traditional_chinese_model(),
+            latin,
+        ];
+        for m in models {
+            for input in [
+                "",
+                "a",
+                "Hello, world!",
+                "The quick brown fox jumps over the lazy dog.",
+                "well-known  and/or\tnot\n(1 + 2) * 3 = 9;",
+            ] {
+                for threshold in [i32::MIN, -5000, super::DEFAULT_THRESHOLD, 5000] {
+                    let offsets = input.char_indices().skip(1).map(|(i, _)| i);
+                    let boundaries = offsets
+                        .zip(m.scores(input))
+                        .filter(|&(_, score)| score >= threshold)
+                        .map(|(i, _)| i);
+                    let expected = super::split_chunks(input, boundaries).collect::<Vec<_>>();
+                    assert_eq!(m.parse_with_threshold(input, threshold), expected);
+                    assert_eq!(
+                        m.boundaries_with_threshold(input, threshold).len() + 1,
+                        expected.len().max(1)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_break_probabilities() {
+        let m = super::models::default_japanese_model();
+        let input = "これはテストです。今日は晴天です。";
+
+        assert_eq!(m.break_probabilities(""), Vec::<f32>::new());
+        assert_eq!(m.break_probabilities("あ"), Vec::<f32>::new());
+
+        let scores = m.scores(input);
+        let probabilities = m.break_probabilities(input);
+        assert_eq!(probabilities.len(), scores.len());
+        for (&score, &p) in scores.iter().zip(&probabilities) {
+            assert!((0.0..=1.0).contains(&p));
+            assert_eq!(p > 0.5, score > 0);
+            let expected = 1.0 / (1.0 + (-f64::from(score) / 1000.0).exp());
+            assert!((f64::from(p) - expected).abs() < 1e-6);
+        }
+        // probabilities are monotonic in scores.
+        let mut pairs = scores.into_iter().zip(probabilities).collect::<Vec<_>>();
+        pairs.sort_by_key(|&(score, _)| score);
+        assert!(pairs.windows(2).all(|w| w[0].1 <= w[1].1));
+
+        // the scale follows the score multiplier.
+        let doubled = m.clone().with_score_multiplier(4);
+        for (a, b) in doubled
+            .break_probabilities(input)
+            .iter()
+            .zip(m.break_probabilities(input))
+        {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_scores() {
+        let m = super::models::default_japanese_model();
+
+        assert_eq!(m.scores(""), Vec::<i32>::new());
+        assert_eq!(m.scores("あ"), Vec::<i32>::new());
+
+        let input = "これはテストです。今日は晴天です。";
+        let scores = m.scores(input);
+        assert_eq!(scores.len(), input.chars().count() - 1);
+
+        let boundaries = input
+            .char_indices()
+            .skip(1)
+            .zip(scores)
+            .filter(|(_, score)| *score >= super::DEFAULT_THRESHOLD)
+            .map(|((i, _), _)| i)
+            .collect::<Vec<_>>();
+        assert_eq!(boundaries, m.parse_boundaries(input));
+    }
+
+    #[test]
+    fn test_from_slice() {
+        let json = r#"{"UW4": {"油": 100}, "BW2": {"と": -10}}"#;
+
+        assert_eq!(
+            super::Model::from_slice(json.as_bytes()).unwrap(),
+            super::Model::from_str(json).unwrap()
+        );
+        assert!(super::Model::from_slice(b"{").is_err());
+    }
+
+    #[test]
+    fn test_to_string() {
+        let m = super::models::default_japanese_model();
+        let json = m.to_string().unwrap();
+
+        assert_eq!(&super::Model::from_str(&json).unwrap(), m);
+
+        #[cfg(feature = "std")]
+        {
+            let mut buf = vec![];
+            m.to_writer(&mut buf).unwrap();
+            assert_eq!(&super::Model::from_slice(&buf).unwrap(), m);
+        }
+    }
+
+    #[test]
+    fn test_feature_groups() {
+        let m = super::models::default_japanese_model();
+
+        assert_eq!(
+            m.feature_groups(),
+            vec![
+                "BW1", "BW2", "BW3", "TW1", "TW2", "TW3", "TW4", "UW1", "UW2", "UW3", "UW4", "UW5",
+                "UW6"
+            ]
+        );
+        assert_eq!(
+            super::Model::from_str("{}").unwrap().feature_groups(),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        use super::{Model, ModelError};
+
+        assert_eq!(super::models::default_japanese_model().validate(), Ok(()));
+        assert_eq!(super::models::default_thai_model().validate(), Ok(()));
+        assert_eq!(Model::from_str("{}").unwrap().validate(), Ok(()));
+        assert_eq!(
+            Model::from_str(r#"{"UW1": {}, "TW5": {}, "BW4": {}}"#)
+                .unwrap()
+                .validate(),
+            Err(ModelError::UnknownFeature(String::from("BW4")))
+        );
+    }
+
+    #[test]
+    fn test_parse_batch() {
+        let m = super::models::default_japanese_model();
+        let inputs = ["", "日本語", "水と油", "これはテストです。今日は晴天です。"];
+
+        assert_eq!(m.parse_batch(&[]), Vec::<Vec<&str>>::new());
+        assert_eq!(
+            m.parse_batch(&inputs),
+            inputs.iter().map(|s| m.parse(s)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_parse_into() {
+        let m = super::models::default_japanese_model();
+        let mut out = vec!["stale"];
+
+        m.parse_into("", &mut out);
+        assert_eq!(out, Vec::<&str>::new());
+        m.parse_into("これはテストです。今日は晴天です。", &mut out);
+        assert_eq!(out, m.parse("これはテストです。今日は晴天です。"));
+        m.parse_into("日本語", &mut out);
+        assert_eq!(out, vec!["日本語"]);
+    }
+
+    #[test]
+    fn test_serde() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Config {
+            name: String,
+            model: super::Model,
+        }
+
+        let m = super::models::default_japanese_model();
+        let json = serde_json::to_string(&serde_json::json!({ "name": "ja", "model": m })).unwrap();
+        let config: Config = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(config.name, "ja");
+        assert_eq!(&config.model, m);
+        assert_eq!(
+            &serde_json::from_value::<super::Model>(serde_json::to_value(m).unwrap()).unwrap(),
+            m
+        );
+    }
+
+    #[test]
+    fn test_parse_with_scores() {
+        let m = super::models::default_japanese_model();
+
+        assert_eq!(m.parse_with_scores(""), vec![]);
+        assert_eq!(m.parse_with_scores("日本語"), vec![("日本語", None)]);
+
+        let input = "これはテストです。今日は晴天です。";
+        let words = m.parse_with_scores(input);
+        assert_eq!(
+            words.iter().map(|(w, _)| *w).collect::<Vec<_>>(),
+            m.parse(input)
+        );
+        let scores = m.scores(input);
+        for (i, (_, score)) in m.parse_boundaries(input).into_iter().zip(&words[1..]) {
+            let index = input[..i].chars().count() - 1;
+            assert_eq!(*score, Some(scores[index]));
+        }
+    }
+
+    #[test]
+    fn test_count_boundaries() {
+        let m = super::models::default_japanese_model();
+
+        assert_eq!(m.count_boundaries(""), 0);
+        assert_eq!(m.count_boundaries("あ"), 0);
+        for input in [
+            "水と油",
+            "日本語の文章において語の区切りに空白を挟んで記述すること",
+            "これはテストです。\n今日は晴天です。",
+        ] {
+            assert_eq!(m.count_boundaries(input), m.parse(input).len() - 1);
+        }
+    }
+
+    #[test]
+    fn test_score_iter() {
+        let m = super::models::default_simplified_chinese_model();
+
+        for input in [
+            "",
+            "你",
+            "你好",
+            "你喜欢看アニメ吗",
+            "今天是晴天。你喜欢看アニメ吗",
+        ] {
+            let chars = input.char_indices().collect::<Vec<_>>();
+            let expected = (1..chars.len())
+                .map(|i| (chars[i].0, m.score(input, &chars, i)))
+                .collect::<Vec<_>>();
+            assert_eq!(m.score_iter(input).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn test_parse_graphemes() {
+        let m = super::models::default_japanese_model();
+
+        assert_eq!(m.parse_graphemes(""), Vec::<&str>::new());
+        for input in [
+            "日本語の文章において語の区切りに空白を挟んで記述すること",
+            "これはテストです。\n今日は晴天です。",
+        ] {
+            assert_eq!(m.parse_graphemes(input), m.parse(input));
+        }
+        // \r\n is a single grapheme cluster.
+        assert_eq!(
+            m.parse_graphemes("これはテストです。\r\n今日は晴天です。"),
+            vec!["これは", "テストです。", "\r\n今日は", "晴天です。"]
+        );
+        for input in ["これは👨‍👩‍👧‍👦家族です", "か\u{3099}な", "ポ\u{309a}ケット"]
+        {
+            let words = m.parse_graphemes(input);
+            assert_eq!(words.concat(), input);
+            for word in words {
+                assert!(!word.starts_with(['\u{200d}', '\u{3099}', '\u{309a}']));
+                assert!(!word.starts_with(['👩', '👧', '👦']));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_bigram() {
+        let m = super::Model::from_str(r#"{"BW2": {"水と": 10000}}"#).unwrap();
+
+        assert_eq!(m.parse("水と油"), vec!["水", "と油"]);
+        assert_eq!(m.parse("油と水"), vec!["油と水"]);
+    }
+
+    #[test]
+    fn test_parse_astral() {
+        let input = "𝐀𝐁あ𝒜い🀄漢字𠮷野家";
+        for m in [
+            super::models::default_japanese_model(),
+            super::models::default_thai_model(),
+            super::models::default_simplified_chinese_model(),
+            super::models::default_traditional_chinese_model(),
+        ] {
+            assert_eq!(m.parse(input).concat(), input);
+            assert_eq!(
+                m.parse_with_threshold(input, i32::MIN),
+                input
+                    .char_indices()
+                    .map(|(i, c)| &input[i..i + c.len_utf8()])
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[cfg(feature = "flate2")]
+    #[test]
+    fn test_from_gz_reader() {
+        use std::io::Write;
+
+        let json = include_str!("../resources/ja.json");
+        let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+        encoder.write_all(json.as_bytes()).unwrap();
+        let gz = encoder.finish().unwrap();
+
+        assert_eq!(
+            &super::Model::from_gz_reader(gz.as_slice()).unwrap(),
+            super::models::default_japanese_model()
+        );
+        assert!(super::Model::from_gz_reader(json.as_bytes()).is_err());
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_from_zst_reader() {
+        let json = include_str!("../resources/ja.json");
+        let zst = zstd::encode_all(json.as_bytes(), 0).unwrap();
+
+        assert_eq!(
+            &super::Model::from_zst_reader(zst.as_slice()).unwrap(),
+            super::models::default_japanese_model()
+        );
+        assert!(super::Model::from_zst_reader(json.as_bytes()).is_err());
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn test_bincode() {
+        use super::Model;
+
+        for m in [
+            super::models::default_japanese_model(),
+            super::models::default_simplified_chinese_model(),
+            super::models::default_traditional_chinese_model(),
+            super::models::default_thai_model(),
+        ] {
+            let bytes = m.to_bincode().unwrap();
+            let json = m.to_string().unwrap();
+            assert_eq!(
+                Model::from_bincode(&bytes).unwrap(),
+                Model::from_str(&json).unwrap()
+            );
+        }
+        assert!(Model::from_bincode(&[0xff]).is_err());
+    }
+
+    #[test]
+    fn test_parse_ranges() {
+        let m = super::models::default_japanese_model();
+
+        assert_eq!(m.parse_ranges(""), vec![]);
+        let input = "これはテストです。今日は晴天です。";
+        let words = m.parse_ranges(input);
+        assert_eq!(
+            words.iter().map(|(_, w)| *w).collect::<Vec<_>>(),
+            m.parse(input)
+        );
+        for (range, word) in words {
+            assert_eq!(&input[range], word);
+        }
+    }
+
+    #[test]
+    fn test_parse_concat() {
+        // xorshift generates reproducible pseudo random inputs without extra dependencies.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let alphabet = [
+            "これはテストです。",
+            "今天是晴天。",
+            "วันนี้อากาศดี",
+            "مرحبا بالعالم",
+            "BudouX \t\r\n",
+            "👨‍👩‍👧‍👦🇯🇵é",
+        ]
+        .concat()
+        .chars()
+        .collect::<Vec<_>>();
+
+        for m in [
+            super::models::default_japanese_model(),
+            super::models::default_simplified_chinese_model(),
+            super::models::default_traditional_chinese_model(),
+            super::models::default_thai_model(),
+        ] {
+            for _ in 0..200 {
+                let len = next() % 40;
+                let input = (0..len)
+                    .map(|_| match next() % 4 {
+                        // any scalar value, including astral planes.
+                        0 => char::from_u32((next() % 0x11_0000) as u32).unwrap_or('\u{fffd}'),
+                        _ => alphabet[next() as usize % alphabet.len()],
+                    })
+                    .collect::<String>();
+                let words = m.parse(&input);
+                assert_eq!(words.concat(), input);
+                assert!(words.iter().all(|w| !w.is_empty()), "{input:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_with() {
+        use alloc::{rc::Rc, sync::Arc};
+
+        let m = super::models::default_japanese_model();
+        let owned = super::Model::from_str(&m.to_string().unwrap()).unwrap();
+        let input = "これはテストです。";
+
+        assert_eq!(super::parse_with(m, input), m.parse(input));
+        assert_eq!(super::parse_with(&owned, input), m.parse(input));
+        let rc = Rc::new(super::Model::from_str(&m.to_string().unwrap()).unwrap());
+        assert_eq!(super::parse_with(rc, input), m.parse(input));
+        let arc = Arc::new(owned);
+        assert_eq!(super::parse_with(arc, input), m.parse(input));
+    }
+
+    #[test]
+    fn test_merge_chunks() {
+        use alloc::collections::BTreeSet;
+
+        let compounds = ["日本経済", "日本経済新聞", "晴天"]
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+        let in_dictionary = |a: &str, b: &str| compounds.contains([a, b].concat().as_str());
+
+        assert_eq!(
+            super::merge_chunks(&[], in_dictionary),
+            Vec::<String>::new()
+        );
+        assert_eq!(super::merge_chunks(&["日本"], in_dictionary), vec!["日本"]);
+        assert_eq!(
+            super::merge_chunks(&["日本", "経済", "新聞", "を", "読む"], in_dictionary),
+            vec!["日本経済新聞", "を", "読む"]
+        );
+        assert_eq!(
+            super::merge_chunks(&["晴", "天", "と", "晴", "天"], in_dictionary),
+            vec!["晴天", "と", "晴天"]
+        );
+        assert_eq!(
+            super::merge_chunks(&["経済", "新聞"], in_dictionary),
+            vec!["経済", "新聞"]
+        );
+
+        let m = super::models::default_japanese_model();
+        let input = "これはテストです。今日は晴天です。";
+        assert_eq!(
+            super::merge_chunks(&m.parse(input), |_, _| false),
+            m.parse(input)
+        );
+        assert_eq!(
+            super::merge_chunks(&m.parse(input), |_, _| true),
+            vec![input]
+        );
+    }
+
+    #[test]
+    fn test_parse_with_classifier() {
+        let m = super::models::default_japanese_model();
+
+        for input in ["", "日本語", "これはテストです。今日は晴天です。"] {
+            assert_eq!(m.parse_with_classifier(input, |_| None), m.parse(input));
+            assert_eq!(m.parse_with_classifier(input, |_| Some("")).concat(), input);
+        }
+
+        let m =
+            super::Model::from_str(r#"{"UW1": {"\u0000": -2000}, "UW4": {"N": 1000, "AB": 1000}}"#)
+                .unwrap();
+        let digit = |c: char| c.is_ascii_digit().then_some("N");
+        assert_eq!(m.parse("a1b23"), vec!["a1b23"]);
+        assert_eq!(
+            m.parse_with_classifier("a1b23", digit),
+            vec!["a", "1b", "2", "3"]
+        );
+        // keys longer than a character.
+        let word = |c: char| (c == 'x').then_some("AB");
+        assert_eq!(m.parse_with_classifier("axc", word), vec!["a", "xc"]);
+    }
+
+    #[cfg(feature = "unicode-width")]
     #[test]
-    fn test_parse() {
+    fn test_parse_columns() {
+        use unicode_width::UnicodeWidthStr;
+
         let m = super::models::default_japanese_model();
 
-        assert_eq!(m.parse(""), Vec::<&str>::new());
-        assert_eq!(m.parse("日本語"), vec!["日本語"]);
-        assert_eq!(m.parse("水と油"), vec!["水と", "油"]);
+        assert_eq!(m.parse_columns("", 4), Vec::<&str>::new());
         assert_eq!(
-            m.parse("水道水とミネラルウォーター"),
-            vec!["水道水と", "ミネラルウォーター"]
+            m.parse_columns("これはテストです。", 100),
+            m.parse("これはテストです。")
         );
+        assert_eq!(m.parse_columns("日本語", 0), vec!["日", "本", "語"]);
+        assert_eq!(m.parse_columns("日本語", 3), vec!["日", "本", "語"]);
+        assert_eq!(m.parse_columns("abcdef", 4), vec!["abcd", "ef"]);
+        assert_eq!(m.parse_columns("か\u{3099}な", 2), vec!["か\u{3099}", "な"]);
+        let input = "日本語の文章において語の区切りに空白を挟んで記述すること";
+        for max_cols in 2..20 {
+            let words = m.parse_columns(input, max_cols);
+            assert_eq!(words.concat(), input);
+            assert!(words.iter().all(|w| w.width() <= max_cols));
+        }
+    }
+
+    #[test]
+    fn test_try_parse() {
+        let m = super::models::default_japanese_model();
+
+        for input in ["", "日本語", "これはテストです。今日は晴天です。", "👨‍👩‍👧‍👦🇯🇵"]
+        {
+            assert_eq!(m.try_parse(input), Ok(m.parse(input)));
+        }
+    }
+
+    #[test]
+    fn test_try_split_chunks() {
+        use super::{try_split_chunks, ParseError};
+
         assert_eq!(
-            m.parse("PythonとJavaScriptとGolang"),
-            vec!["Pythonと", "JavaScriptと", "Golang"]
+            try_split_chunks("水と油", [6].into_iter()),
+            Ok(vec!["水と", "油"])
         );
         assert_eq!(
-            m.parse("日本語の文章において語の区切りに空白を挟んで記述すること"),
-            vec![
-                "日本語の",
-                "文章に",
-                "おいて",
-                "語の",
-                "区切りに",
-                "空白を",
-                "挟んで",
-                "記述する",
-                "こと"
-            ]
+            try_split_chunks("水と油", [4].into_iter()),
+            Err(ParseError::InvalidBoundary(4))
         );
         assert_eq!(
-            m.parse("これはテストです。"),
-            vec!["これは", "テストです。"]
+            try_split_chunks("水と油", [6, 3].into_iter()),
+            Err(ParseError::InvalidBoundary(3))
         );
         assert_eq!(
-            m.parse("これは美しいペンです。"),
-            vec!["これは", "美しい", "ペンです。"]
+            try_split_chunks("水と油", [3, 3].into_iter()),
+            Err(ParseError::InvalidBoundary(3))
         );
-        assert_eq!(m.parse("今日は天気です。"), vec!["今日は", "天気です。"]);
         assert_eq!(
-            m.parse("今日はとても天気です。"),
-            vec!["今日は", "とても", "天気です。"]
+            try_split_chunks("水と油", [9].into_iter()),
+            Err(ParseError::InvalidBoundary(9))
         );
         assert_eq!(
-            m.parse("あなたに寄り添う最先端のテクノロジー。"),
-            vec!["あなたに", "寄り添う", "最先端の", "テクノロジー。"]
+            try_split_chunks("水と油", [10].into_iter()),
+            Err(ParseError::InvalidBoundary(10))
         );
+    }
+
+    #[test]
+    fn test_join() {
+        let m = super::models::default_japanese_model();
+
+        assert_eq!(m.join("", "/"), "");
+        for input in ["日本語", "これはテストです。今日は晴天です。", "水と油"]
+        {
+            for delimiter in ["", "/", " | "] {
+                assert_eq!(m.join(input, delimiter), m.parse(input).join(delimiter));
+            }
+        }
+    }
+
+    #[test]
+    fn test_feature_groups_context() {
+        // the sliding window of scoring holds 3 units on each side of the boundary.
+        for (group, offset, len) in super::FEATURE_GROUPS {
+            assert!(offset >= -3 && offset + len as isize <= 3, "{group}");
+        }
+    }
+
+    #[test]
+    fn test_fill_scores() {
+        let m = super::models::default_japanese_model();
+        let input = "これはテストです。今日は晴天です。";
+        let scores = m.scores(input);
+
+        let mut buf = [i32::MAX; 4];
+        assert_eq!(m.fill_scores("", &mut buf), 0);
+        assert_eq!(m.fill_scores("日", &mut buf), 0);
+        assert_eq!(buf, [i32::MAX; 4]);
+        assert_eq!(m.fill_scores(input, &mut buf), 4);
+        assert_eq!(buf, scores[..4]);
+        assert_eq!(m.fill_scores(input, &mut []), 0);
+
+        let mut buf = vec![0; 100];
+        assert_eq!(m.fill_scores(input, &mut buf), scores.len());
+        assert_eq!(buf[..scores.len()], scores);
+    }
+
+    #[test]
+    fn test_score_at() {
+        let m = super::models::default_japanese_model();
+
+        assert_eq!(m.score_at("", 0), None);
+        assert_eq!(m.score_at("日", 1), None);
+        assert_eq!(m.score_at("日本語", usize::MAX), None);
+        for input in [
+            "水と油",
+            "これはテストです。今日は晴天です。",
+            "日本語の文章において語の区切りに空白を挟んで記述すること",
+        ] {
+            let scores = m.scores(input);
+            assert_eq!(m.score_at(input, 0), None);
+            assert_eq!(m.score_at(input, scores.len() + 1), None);
+            for (i, &score) in scores.iter().enumerate() {
+                assert_eq!(m.score_at(input, i + 1), Some(score));
+            }
+        }
+    }
+
+    #[test]
+    fn test_nearest_break() {
+        let m = super::models::default_japanese_model();
+        let input = "これはテストです。今日は晴天です。";
+        let boundaries = m.parse_boundaries(input);
+
+        assert_eq!(m.nearest_break("", 0), None);
+        assert_eq!(m.nearest_break("日本語", 100), None);
+        for target in 0..input.len() + 10 {
+            let expected = boundaries
+                .iter()
+                .copied()
+                .min_by_key(|&b| (b.abs_diff(target), b));
+            assert_eq!(m.nearest_break(input, target), expected, "{target}");
+        }
+        // ties resolve toward the earlier boundary.
+        let (a, b) = (boundaries[0], boundaries[1]);
+        if (a + b) % 2 == 0 {
+            assert_eq!(m.nearest_break(input, (a + b) / 2), Some(a));
+        }
+    }
+
+    #[test]
+    fn test_has_break_between() {
+        let m = super::models::default_japanese_model();
+
+        assert!(!m.has_break_between("", 0));
+        assert!(!m.has_break_between("", 1));
+        assert!(!m.has_break_between("日本語", 0));
+        assert!(!m.has_break_between("日本語", usize::MAX));
+        for input in [
+            "水と油",
+            "これはテストです。今日は晴天です。",
+            "日本語の文章において語の区切りに空白を挟んで記述すること",
+        ] {
+            let scores = m.scores(input);
+            for i in 1..input.chars().count() {
+                assert_eq!(
+                    m.has_break_between(input, i),
+                    scores[i - 1] >= super::DEFAULT_THRESHOLD
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_iter() {
+        use super::Model;
+
+        let m = super::models::default_japanese_model();
+        let triples = m.as_inner().iter().flat_map(|(group, features)| {
+            features
+                .iter()
+                .map(move |(key, &score)| (group.clone(), key.clone(), score))
+        });
+
+        assert_eq!(&Model::from_iter(triples), m);
+        assert_eq!(Model::from_iter([]), Model::new(super::HashMap::new()));
         assert_eq!(
-            m.parse("これはテストです。今日は晴天です。"),
-            vec!["これは", "テストです。", "今日は", "晴天です。"]
+            Model::from_iter([
+                (String::from("UW1"), String::from("a"), 1),
+                (String::from("UW1"), String::from("a"), -3),
+                (String::from("UW2"), String::from("a"), 5),
+            ])
+            .base_score(),
+            -3
+        );
+    }
+
+    #[test]
+    fn test_memory_bytes() {
+        use super::Model;
+
+        let empty = Model::from_str("{}").unwrap();
+        let m = super::models::default_japanese_model();
+        let keys = m
+            .as_inner()
+            .iter()
+            .map(|(group, features)| group.len() + features.keys().map(String::len).sum::<usize>())
+            .sum::<usize>();
+
+        assert_eq!(empty.memory_bytes(), 0);
+        assert!(
+            m.memory_bytes() > keys + m.feature_count() * core::mem::size_of::<(String, i32)>()
+        );
+        assert!(
+            m.memory_bytes() < super::models::default_simplified_chinese_model().memory_bytes()
+        );
+        assert!(m.pruned(1000).memory_bytes() < m.memory_bytes());
+    }
+
+    #[test]
+    fn test_feature_count() {
+        use super::Model;
+
+        assert_eq!(Model::from_str("{}").unwrap().feature_count(), 0);
+        assert!(Model::from_str("{}").unwrap().is_empty());
+        assert!(Model::from_str(r#"{"UW1": {}}"#).unwrap().is_empty());
+        for m in [
+            super::models::default_japanese_model(),
+            super::models::default_thai_model(),
+        ] {
+            assert!(!m.is_empty());
+            assert_eq!(
+                m.feature_count(),
+                m.as_inner()
+                    .values()
+                    .flat_map(|features| features.keys())
+                    .count()
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_str_trait() {
+        fn parse<T: core::str::FromStr>(s: &str) -> Result<T, T::Err> {
+            s.parse()
+        }
+
+        let m = super::models::default_japanese_model();
+        let json = m.to_string().unwrap();
+
+        assert_eq!(&parse::<super::Model>(&json).unwrap(), m);
+        assert!(parse::<super::Model>("{").is_err());
+    }
+
+    #[test]
+    fn test_deterministic() {
+        use super::Model;
+
+        let m = super::models::default_japanese_model();
+        let mut triples = m
+            .as_inner()
+            .iter()
+            .flat_map(|(group, features)| {
+                features
+                    .iter()
+                    .map(move |(key, &score)| (group.clone(), key.clone(), score))
+            })
+            .collect::<Vec<_>>();
+        triples.sort();
+        let sorted = triples.iter().cloned().collect::<Model>();
+        let reversed = triples.iter().rev().cloned().collect::<Model>();
+        // every HashMap has its own hasher seed, so the iteration order differs between models.
+        let reloaded = Model::from_str(&m.to_string().unwrap()).unwrap();
+
+        for other in [&sorted, &reversed, &reloaded] {
+            assert_eq!(other.base_score(), m.base_score());
+            for input in [
+                "これはテストです。今日は晴天です。",
+                "日本語の文章において語の区切りに空白を挟んで記述すること",
+                "あなたに寄り添う最先端のテクノロジー。",
+            ] {
+                assert_eq!(other.parse(input), m.parse(input));
+                assert_eq!(other.scores(input), m.scores(input));
+            }
+        }
+    }
+
+    #[test]
+    fn test_score_overflow() {
+        use super::Model;
+
+        let m = Model::from_str(
+            r#"{"UW3": {"a": 2000000000}, "UW4": {"b": 2000000000}, "UW5": {"c": 2000000000}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(m.base_score(), i32::MIN);
+        // -6e9 + 4e9 + 4e9 does not wrap around.
+        assert_eq!(m.parse("ab"), vec!["a", "b"]);
+        assert_eq!(m.scores("ab"), vec![2000000000]);
+        assert_eq!(m.parse("abc"), vec!["a", "bc"]);
+        assert_eq!(m.scores("abc"), vec![i32::MAX, i32::MIN]);
+        assert_eq!(m.parse("xbx"), vec!["xbx"]);
+        assert_eq!(m.parse("xx"), vec!["xx"]);
+        assert_eq!(m.scores("xx"), vec![i32::MIN]);
+        assert_eq!(m.explain("abc")[0].score, i32::MAX);
+
+        let m = [
+            (String::from("UW4"), String::from("b"), i32::MAX),
+            (String::from("UW4"), String::from("b"), i32::MAX),
+        ]
+        .into_iter()
+        .collect::<Model>();
+        assert_eq!(m.as_inner()["UW4"]["b"], i32::MAX);
+    }
+
+    #[test]
+    fn test_pruned() {
+        use super::Model;
+
+        let m = Model::from_str(r#"{"UW1": {"a": 1, "b": -5}, "UW2": {"c": 3}, "UW3": {"d": -2}}"#)
+            .unwrap();
+
+        assert_eq!(m.pruned(0), m);
+        assert_eq!(m.pruned(i32::MIN), m);
+        assert_eq!(
+            m.pruned(3),
+            Model::from_str(r#"{"UW1": {"b": -5}, "UW2": {"c": 3}}"#).unwrap()
         );
+        assert_eq!(m.pruned(3).base_score(), 2);
+        assert!(m.pruned(6).is_empty());
+        assert!(m.pruned(6).as_inner().is_empty());
+        assert_eq!(m.pruned_with_count(0), (m.clone(), 0));
+        assert_eq!(m.pruned_with_count(3).1, 2);
+        assert_eq!(m.pruned_with_count(6).1, 4);
+
+        let m = super::models::default_japanese_model();
+        let (pruned, removed) = m.pruned_with_count(50);
+        assert!(removed > 0);
+        assert_eq!(pruned.feature_count() + removed, m.feature_count());
+        assert_eq!(pruned, m.pruned(50));
+        assert!(pruned
+            .as_inner()
+            .values()
+            .flat_map(|features| features.values())
+            .all(|score| score.abs() >= 50));
+    }
+
+    #[test]
+    fn test_clone() {
+        let m = super::models::default_japanese_model();
+        let cloned = m.clone();
+
+        assert_eq!(&cloned, m);
+        assert_eq!(cloned.base_score(), m.base_score());
+        let modified = super::ModelBuilder::from_model(&cloned)
+            .add_feature("UW4", "🍣", 1)
+            .build();
+        assert_ne!(&modified, m);
+        assert_eq!(&cloned, m);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_hash() {
+        use std::{
+            collections::{hash_map::DefaultHasher, HashSet},
+            hash::{Hash, Hasher},
+        };
+
+        fn hash(m: &super::Model) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            m.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let ja = super::models::default_japanese_model();
+        let reloaded = super::Model::from_str(&ja.to_string().unwrap()).unwrap();
+        assert_eq!(hash(ja), hash(&reloaded));
+        assert_eq!(ja.content_hash(), reloaded.content_hash());
+
+        let models = [
+            super::Model::from_str("{}").unwrap(),
+            super::Model::from_str(r#"{"UW1": {"ab": 1}}"#).unwrap(),
+            super::Model::from_str(r#"{"UW1": {"ab": 2}}"#).unwrap(),
+            super::Model::from_str(r#"{"UW1": {"a": 1}, "UW2": {"b": 1}}"#).unwrap(),
+            super::Model::from_str(r#"{"UW1": {"a": 1, "b": 1}}"#).unwrap(),
+            ja.clone(),
+            super::models::default_thai_model().clone(),
+        ];
+        let hashes = models
+            .iter()
+            .map(|m| m.content_hash())
+            .collect::<HashSet<_>>();
+        assert_eq!(hashes.len(), models.len());
+        assert_eq!(models.iter().collect::<HashSet<_>>().len(), models.len());
+        // content_hash is stable across releases.
+        assert_eq!(models[0].content_hash(), 0xcbf2_9ce4_8422_2325);
         assert_eq!(
-            m.parse("これはテストです。\n今日は晴天です。"),
-            vec!["これは", "テストです。", "\n今日は", "晴天です。"]
+            super::Model::from_str(r#"{"UW1": {"a": 1}}"#)
+                .unwrap()
+                .content_hash(),
+            0x34e8_fdee_4cfa_8494
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_error() {
+        use super::{Error, Model, ModelError};
+        use std::io;
+
+        assert!(matches!(Model::from_str("{"), Err(Error::Json(_))));
+        assert!(matches!(
+            Model::from_slice(br#"{"UW1": 1}"#),
+            Err(Error::Json(_))
+        ));
+        assert!(matches!(
+            Model::from_flat_json(r#"{"UW1": 1}"#),
+            Err(Error::InvalidModel(ModelError::UnknownFeature(name))) if name == "UW1"
+        ));
+
+        struct ErrorReader;
+
+        impl io::Read for ErrorReader {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("error"))
+            }
+        }
+
+        let err = Model::from_reader(ErrorReader).unwrap_err();
+        assert!(matches!(&err, Error::Io(e) if e.kind() == io::ErrorKind::Other));
+        assert_eq!(err.to_string(), "read model: error");
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn test_from_float_json() {
+        use super::Model;
+
+        let m = super::models::default_japanese_model();
+        let json = m.to_string().unwrap();
+
+        // integer models are loaded as is with a scale of 1.
+        assert_eq!(&Model::from_float_json(&json, 1).unwrap(), m);
+        assert_eq!(
+            Model::from_float_json("{}", 1000).unwrap(),
+            Model::from_str("{}").unwrap()
+        );
+
+        let m = Model::from_float_json(
+            r#"{"UW1": {"a": 0.0015, "b": -0.0015, "c": 1e10, "d": -1e10}, "UW2": {"e": 2}}"#,
+            1000,
+        )
+        .unwrap();
+        let uw1 = &m.as_inner()["UW1"];
+        assert_eq!((uw1["a"], uw1["b"]), (2, -2));
+        assert_eq!((uw1["c"], uw1["d"]), (i32::MAX, i32::MIN));
+        assert_eq!(m.as_inner()["UW2"]["e"], 2000);
+
+        assert!(Model::from_float_json(r#"{"UW1": {"a": "1"}}"#, 1).is_err());
+    }
+
+    #[test]
+    fn test_from_flat_json() {
+        use super::Model;
+
+        let m = super::models::default_japanese_model();
+        let flat = m
+            .as_inner()
+            .iter()
+            .flat_map(|(group, features)| {
+                features
+                    .iter()
+                    .map(move |(key, &score)| (alloc::format!("{}:{}", group, key), score))
+            })
+            .collect::<super::HashMap<_, _>>();
+        let json = serde_json::to_string(&flat).unwrap();
+
+        assert_eq!(&Model::from_flat_json(&json).unwrap(), m);
+        assert_eq!(
+            Model::from_flat_json("{}").unwrap(),
+            Model::from_str("{}").unwrap()
+        );
+        assert!(Model::from_flat_json(r#"{"UW1": 1}"#).is_err());
+        assert!(Model::from_flat_json(r#"{"UW1": {"a": 1}}"#).is_err());
+    }
+
+    #[test]
+    fn test_recompute_base_score() {
+        use super::Model;
+
+        let mut m = super::models::default_japanese_model().clone();
+        let uw4 = m.as_inner_mut().get_mut("UW4").unwrap();
+        *uw4.entry(String::from("油")).or_insert(0) += 5000;
+        uw4.insert(String::from("☃"), -300);
+        m.as_inner_mut().remove("TW4");
+
+        let fresh = Model::new(m.as_inner().clone());
+        assert_ne!(m, fresh);
+        m.recompute_base_score();
+        assert_eq!(m, fresh);
+        assert_eq!(m.base_score(), fresh.base_score());
+        assert_eq!(m.parse("水と油"), fresh.parse("水と油"));
+    }
+
+    #[test]
+    fn test_parse_minimal_input() {
+        use super::models::*;
+
+        let models = [
+            default_japanese_model(),
+            default_thai_model(),
+            default_simplified_chinese_model(),
+            default_traditional_chinese_model(),
+            default_latin_model(),
+        ];
+        for m in models {
+            // a single character has no boundary, so it is never split.
+            assert_eq!(m.parse("あ"), vec!["あ"]);
+            assert_eq!(m.parse("a"), vec!["a"]);
+
+            // two characters have a single boundary, scored by UW3, UW4 and BW2 only. features are
+            // doubled by SCORE_MULTIPLIER against the base score, which is minus the sum of every
+            // feature.
+            for input in ["ああ", "aa", "a ", " a"] {
+                let chars = input.chars().map(String::from).collect::<Vec<_>>();
+                let feature = |group: &str, key: &str| {
+                    m.as_inner()
+                        .get(group)
+                        .and_then(|features| features.get(key))
+                        .map_or(0, |&score| i64::from(score))
+                };
+                let score = super::saturate(
+                    m.base_score
+                        + 2 * (feature("UW3", &chars[0])
+                            + feature("UW4", &chars[1])
+                            + feature("BW2", input)),
+                );
+                assert_eq!(m.score_at(input, 1), Some(score));
+                let expected = if score >= super::DEFAULT_THRESHOLD {
+                    vec![chars[0].as_str(), chars[1].as_str()]
+                } else {
+                    vec![input]
+                };
+                assert_eq!(m.parse(input), expected, "{input:?}");
+            }
+        }
+
+        // decisions of the bundled models, which are made by the base score and a few features.
+        assert_eq!(default_japanese_model().parse("ああ"), vec!["あ", "あ"]);
+        assert_eq!(default_japanese_model().parse("aa"), vec!["aa"]);
+        assert_eq!(default_thai_model().parse("a "), vec!["a", " "]);
+        assert_eq!(default_latin_model().parse(" a"), vec![" ", "a"]);
+        assert_eq!(default_latin_model().parse("a "), vec!["a "]);
+    }
+
+    #[test]
+    fn test_score_multiplier() {
+        use super::{Model, SCORE_MULTIPLIER};
+
+        let m = super::models::default_japanese_model();
+        let input = "日本語の文章において語の区切りに空白を挟んで記述すること";
+        let sum = m
+            .as_inner()
+            .values()
+            .flat_map(|features| features.values())
+            .map(|&score| i64::from(score))
+            .sum::<i64>();
+
+        // current behavior: features count twice against minus the sum of every feature.
+        assert_eq!(SCORE_MULTIPLIER, 2);
+        assert_eq!(m.score_multiplier(), 2);
+        assert_eq!(i64::from(m.base_score()), -sum);
+        assert_eq!(m.clone().with_score_multiplier(2), *m);
+
+        // a model whose scores are already doubled gives the same scores with a multiplier of 1.
+        let doubled = super::ModelBuilder::new()
+            .merge(m)
+            .merge(m)
+            .build()
+            .with_score_multiplier(1);
+        assert_eq!(doubled.base_score(), m.base_score());
+        assert_eq!(doubled.scores(input), m.scores(input));
+        assert_eq!(doubled.parse(input), m.parse(input));
+
+        // scores scale with the multiplier.
+        let tripled = m.clone().with_score_multiplier(6);
+        assert_eq!(
+            tripled.scores(input),
+            m.scores(input).iter().map(|s| s * 3).collect::<Vec<_>>()
+        );
+        assert_eq!(tripled.parse(input), m.parse(input));
+
+        assert!(Model::from_str("{}")
+            .unwrap()
+            .with_score_multiplier(0)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_base_score() {
+        let m = super::Model::from_str(r#"{"UW4": {"a": 3, "b": -1}, "BW2": {"ab": 2}}"#).unwrap();
+
+        assert_eq!(m.base_score(), -4);
+        assert_eq!(super::Model::new(super::HashMap::new()).base_score(), 0);
+    }
+
+    #[test]
+    fn test_base_score_odd_multiplier() {
+        // the exact base score is 500.5, so a -500 feature scores 0.5 and breaks like upstream.
+        let m = super::Model::from_str(r#"{"UW4": {"a": -500}, "UW1": {"x": -501}}"#)
+            .unwrap()
+            .with_score_multiplier(1);
+
+        assert_eq!(m.base_score(), 501);
+        assert_eq!(m.scores("ba"), vec![1]);
+        assert_eq!(m.parse("ba"), vec!["b", "a"]);
+
+        // odd positive sums round up as well.
+        let m = super::Model::from_str(r#"{"UW4": {"a": 500}, "UW1": {"x": 501}}"#)
+            .unwrap()
+            .with_score_multiplier(1);
+
+        assert_eq!(m.base_score(), -500);
+        assert_eq!(m.scores("ba"), vec![0]);
+        assert_eq!(m.parse("ba"), vec!["ba"]);
+    }
+
+    #[test]
+    fn test_for_language() {
+        use super::{models, Model};
+
+        for (tag, model) in [
+            ("ja", models::default_japanese_model()),
+            ("JA-jp", models::default_japanese_model()),
+            ("th", models::default_thai_model()),
+            ("th_TH", models::default_thai_model()),
+            ("zh-Hans", models::default_simplified_chinese_model()),
+            ("zh_hans_SG", models::default_simplified_chinese_model()),
+            ("zh-CN", models::default_simplified_chinese_model()),
+            ("zh-Hant", models::default_traditional_chinese_model()),
+            ("ZH_HANT", models::default_traditional_chinese_model()),
+            ("zh-TW", models::default_traditional_chinese_model()),
+            ("zh-Hant-CN", models::default_traditional_chinese_model()),
+        ] {
+            assert_eq!(Model::for_language(tag), Some(model), "{tag}");
+        }
+        for tag in ["", "en", "zh", "zh-Latn", "jav", "japanese", "-ja"] {
+            assert_eq!(Model::for_language(tag), None, "{tag}");
+        }
+    }
+
+    #[test]
+    fn test_from_btree() {
+        use alloc::collections::BTreeMap;
+
+        let m = super::models::default_japanese_model();
+        let map = m
+            .as_inner()
+            .iter()
+            .map(|(group, features)| {
+                let features = features
+                    .iter()
+                    .map(|(k, &v)| (k.clone(), v))
+                    .collect::<BTreeMap<_, _>>();
+                (group.clone(), features)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        assert_eq!(&super::Model::from_btree(map), m);
+        assert_eq!(
+            super::Model::from_btree(BTreeMap::new()).parse("日本語"),
+            vec!["日本語"]
         );
     }
 
@@ -225,4 +3808,87 @@ mod tests {
             vec!["你", "喜欢", "看", "アニメ", "吗"]
         )
     }
+
+    /// THAI_SENTENCES are representative Thai sentences with combining vowels and tone marks.
+    const THAI_SENTENCES: [&str; 5] = [
+        "วันนี้อากาศดี",
+        "ฉันกำลังเรียนภาษาไทยที่มหาวิทยาลัย",
+        "ผู้ใหญ่บ้านน้ำใจงาม",
+        "พระอาทิตย์ขึ้นทางทิศตะวันออก",
+        "กรุงเทพมหานครเป็นเมืองหลวงของประเทศไทย",
+    ];
+
+    /// is_thai_combining_mark returns whether c is a Thai combining vowel, tone mark or diacritic.
+    fn is_thai_combining_mark(c: char) -> bool {
+        matches!(c, '\u{e31}' | '\u{e34}'..='\u{e3a}' | '\u{e47}'..='\u{e4e}')
+    }
+
+    #[test]
+    fn test_parse_th() {
+        let m = super::models::default_thai_model();
+
+        assert_eq!(m.parse(""), Vec::<&str>::new());
+        assert_eq!(m.parse("วันนี้อากาศดี"), vec!["วัน", "นี้", "อากาศ", "ดี"]);
+        for input in THAI_SENTENCES {
+            assert_eq!(m.parse(input).concat(), input);
+        }
+    }
+
+    #[test]
+    fn test_parse_th_keeps_clusters() {
+        let m = super::models::default_thai_model();
+
+        for input in THAI_SENTENCES {
+            for word in m.parse(input) {
+                let first = word.chars().next().unwrap();
+                assert!(!is_thai_combining_mark(first), "{word:?} in {input:?}");
+            }
+        }
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn test_parse_graphemes_keeps_emoji() {
+        const FAMILY: &str = "👨\u{200d}👩\u{200d}👧\u{200d}👦";
+
+        let m = super::models::default_japanese_model();
+        let words = m.parse_graphemes("これは👨‍👩‍👧‍👦家族です");
+        assert_eq!(words.iter().filter(|w| w.contains(FAMILY)).count(), 1);
+
+        // a model which breaks before every scalar value of the sequences.
+        // The unused UW1 feature cancels the base score of the others.
+        let m = super::Model::from_str(
+            r#"{
+                "UW1": {"\u0000": -6000},
+                "UW4": {"\u200d": 1000, "👨": 1000, "👩": 1000, "👧": 1000, "👦": 1000, "🌈": 1000, "\ufe0f": 1000}
+            }"#,
+        )
+        .unwrap();
+        for input in ["これは👨‍👩‍👧‍👦家族です", "今日は🏳️‍🌈パレードです"]
+        {
+            assert!(m.parse_with_threshold(input, 1).len() > 2);
+            let words = m.parse_graphemes(input);
+            assert_eq!(words.concat(), input);
+            for word in words {
+                assert!(!word.starts_with(['\u{200d}', '\u{fe0f}', '👩', '👧', '👦', '🌈']));
+            }
+        }
+        assert!(m
+            .parse_graphemes("これは👨‍👩‍👧‍👦家族です")
+            .iter()
+            .any(|w| w.contains(FAMILY)));
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn test_parse_graphemes_th_keeps_clusters() {
+        let m = super::models::default_thai_model();
+
+        for input in THAI_SENTENCES {
+            for word in m.parse_graphemes(input) {
+                let first = word.chars().next().unwrap();
+                assert!(!is_thai_combining_mark(first), "{word:?} in {input:?}");
+            }
+        }
+    }
 }
@@ -0,0 +1,204 @@
+use core::ops::{BitAnd, BitOr, Not, Sub};
+
+use alloc::vec::Vec;
+
+use crate::{split_chunks, Model, DEFAULT_THRESHOLD, FEATURE_GROUPS};
+
+/// FeatureMask is set of feature groups enabled in `Model::parse_with_mask`.
+///
+/// Each bit corresponds to a feature group. Defaults to `FeatureMask::ALL`.
+///
+/// # Examples
+///
+/// ```rust
+/// use budoux::FeatureMask;
+///
+/// let mask = FeatureMask::ALL - FeatureMask::TRIGRAM;
+///
+/// assert!(mask.contains(FeatureMask::UW1 | FeatureMask::BW2));
+/// assert!(!mask.contains(FeatureMask::TW1));
+/// assert_eq!(FeatureMask::from_group("TW1"), Some(FeatureMask::TW1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FeatureMask(u16);
+
+impl FeatureMask {
+    /// UW1 is the unigram feature group of the third character before the boundary.
+    pub const UW1: Self = Self(1 << 0);
+    /// UW2 is the unigram feature group of the second character before the boundary.
+    pub const UW2: Self = Self(1 << 1);
+    /// UW3 is the unigram feature group of the character before the boundary.
+    pub const UW3: Self = Self(1 << 2);
+    /// UW4 is the unigram feature group of the character after the boundary.
+    pub const UW4: Self = Self(1 << 3);
+    /// UW5 is the unigram feature group of the second character after the boundary.
+    pub const UW5: Self = Self(1 << 4);
+    /// UW6 is the unigram feature group of the third character after the boundary.
+    pub const UW6: Self = Self(1 << 5);
+    /// BW1 is the bigram feature group of the two characters before the boundary.
+    pub const BW1: Self = Self(1 << 6);
+    /// BW2 is the bigram feature group of the characters across the boundary.
+    pub const BW2: Self = Self(1 << 7);
+    /// BW3 is the bigram feature group of the two characters after the boundary.
+    pub const BW3: Self = Self(1 << 8);
+    /// TW1 is the trigram feature group of the three characters before the boundary.
+    pub const TW1: Self = Self(1 << 9);
+    /// TW2 is the trigram feature group starting two characters before the boundary.
+    pub const TW2: Self = Self(1 << 10);
+    /// TW3 is the trigram feature group starting one character before the boundary.
+    pub const TW3: Self = Self(1 << 11);
+    /// TW4 is the trigram feature group of the three characters after the boundary.
+    pub const TW4: Self = Self(1 << 12);
+
+    /// NONE is the mask which disables every feature group.
+    pub const NONE: Self = Self(0);
+    /// ALL is the mask which enables every feature group.
+    pub const ALL: Self = Self((1 << FEATURE_GROUPS.len()) - 1);
+    /// UNIGRAM is the unigram feature groups `UW1` to `UW6`.
+    pub const UNIGRAM: Self = Self(0b111111);
+    /// BIGRAM is the bigram feature groups `BW1` to `BW3`.
+    pub const BIGRAM: Self = Self(0b111 << 6);
+    /// TRIGRAM is the trigram feature groups `TW1` to `TW4`.
+    pub const TRIGRAM: Self = Self(0b1111 << 9);
+
+    /// from_group returns the mask of the feature group such as `UW1`, or `None` for unknown groups.
+    pub fn from_group(group: &str) -> Option<Self> {
+        FEATURE_GROUPS
+            .iter()
+            .position(|&(name, _, _)| name == group)
+            .map(Self::from_index)
+    }
+
+    /// contains returns whether every feature group in other is enabled.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// from_index returns the mask of `FEATURE_GROUPS[index]`.
+    pub(crate) fn from_index(index: usize) -> Self {
+        Self(1 << index)
+    }
+}
+
+impl Default for FeatureMask {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl BitOr for FeatureMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for FeatureMask {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl Sub for FeatureMask {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 & !rhs.0)
+    }
+}
+
+impl Not for FeatureMask {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(!self.0) & Self::ALL
+    }
+}
+
+impl Model {
+    /// parse_with_mask returns splitted string slice from input, scoring only the feature groups
+    /// enabled in `enabled`.
+    ///
+    /// Disabled groups contribute 0 to the score, and the base score is unchanged. This is useful to
+    /// measure the contribution of feature groups.
+    ///
+    /// * `input` - input sentences.
+    /// * `enabled` - enabled feature groups.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use budoux::FeatureMask;
+    ///
+    /// let model = budoux::models::default_japanese_model();
+    /// let input = "これはテストです。";
+    ///
+    /// assert_eq!(model.parse_with_mask(input, FeatureMask::ALL), model.parse(input));
+    /// assert_eq!(model.parse_with_mask(input, FeatureMask::NONE), vec![input]);
+    /// ```
+    pub fn parse_with_mask<'i>(&self, input: &'i str, enabled: FeatureMask) -> Vec<&'i str> {
+        let boundaries = self
+            .unit_score_iter(input, input.char_indices(), enabled)
+            .filter(|&(_, score)| score >= DEFAULT_THRESHOLD)
+            .map(|(offset, _)| offset);
+        split_chunks(input, boundaries).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FeatureMask;
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn test_feature_mask() {
+        assert_eq!(FeatureMask::default(), FeatureMask::ALL);
+        assert_eq!(
+            FeatureMask::UNIGRAM | FeatureMask::BIGRAM | FeatureMask::TRIGRAM,
+            FeatureMask::ALL
+        );
+        assert_eq!(
+            FeatureMask::UNIGRAM & FeatureMask::BIGRAM,
+            FeatureMask::NONE
+        );
+        assert_eq!(
+            !FeatureMask::TRIGRAM,
+            FeatureMask::UNIGRAM | FeatureMask::BIGRAM
+        );
+        assert_eq!(!FeatureMask::ALL, FeatureMask::NONE);
+        assert!(FeatureMask::TRIGRAM.contains(FeatureMask::TW4));
+        assert!(FeatureMask::NONE.contains(FeatureMask::NONE));
+        for (i, &(group, _, _)) in crate::FEATURE_GROUPS.iter().enumerate() {
+            assert_eq!(
+                FeatureMask::from_group(group),
+                Some(FeatureMask::from_index(i))
+            );
+        }
+        assert_eq!(FeatureMask::from_group("UB1"), None);
+    }
+
+    #[test]
+    fn test_parse_with_mask() {
+        let m = crate::models::default_japanese_model();
+        let input = "日本語の文章において語の区切りに空白を挟んで記述すること";
+
+        assert_eq!(m.parse_with_mask("", FeatureMask::ALL), Vec::<&str>::new());
+        assert_eq!(m.parse_with_mask(input, FeatureMask::ALL), m.parse(input));
+        assert_eq!(m.parse_with_mask(input, FeatureMask::NONE), vec![input]);
+
+        let without_trigram = m.parse_with_mask(input, FeatureMask::ALL - FeatureMask::TRIGRAM);
+        assert_eq!(without_trigram.concat(), input);
+
+        // disabling a group is the same as removing its features, apart from the base score.
+        let mut map = m.as_inner().clone();
+        for group in ["TW1", "TW2", "TW3", "TW4"] {
+            map.remove(group);
+        }
+        let mut pruned = crate::Model::new(map);
+        pruned.base_score = m.base_score;
+        assert_eq!(pruned.parse(input), without_trigram);
+    }
+}
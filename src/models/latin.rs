@@ -0,0 +1,7 @@
+use super::LazyModel;
+
+/// MODEL reference to hand-authored model which breaks after spaces and dashes.
+pub static MODEL: LazyModel = LazyModel::new(|| crate::Model::from_features(FEATURES));
+
+/// FEATURES is the feature map generated from `resources/latin.json` by the build script.
+static FEATURES: &[(&str, &[(&str, i32)])] = include!(concat!(env!("OUT_DIR"), "/latin.rs"));
@@ -1,6 +1,11 @@
+use core::ops::Deref;
+
 #[path = "ja.rs"]
 mod ja;
 
+#[path = "latin.rs"]
+mod latin;
+
 #[path = "th.rs"]
 mod th;
 
@@ -10,6 +15,94 @@ mod zh_hans;
 #[path = "zh_hant.rs"]
 mod zh_hant;
 
+#[cfg(not(feature = "race"))]
+type Cell = once_cell::sync::OnceCell<crate::Model>;
+#[cfg(feature = "race")]
+type Cell = once_cell::race::OnceBox<crate::Model>;
+
+/// LazyModel is a bundled model which is built by `init` on first use.
+///
+/// By default it is a `once_cell::sync::OnceCell`, which blocks the other threads while one of
+/// them builds the model. With the `race` feature it is a lock-free `once_cell::race::OnceBox`
+/// instead, where racing threads may each build the model and all but the first are dropped.
+pub(crate) struct LazyModel {
+    cell: Cell,
+    init: fn() -> crate::Model,
+}
+
+impl LazyModel {
+    pub(crate) const fn new(init: fn() -> crate::Model) -> Self {
+        Self {
+            cell: Cell::new(),
+            init,
+        }
+    }
+
+    /// force builds the model unless it has been built, and returns it.
+    pub(crate) fn force(&self) -> &crate::Model {
+        #[cfg(not(feature = "race"))]
+        return self.cell.get_or_init(self.init);
+        #[cfg(feature = "race")]
+        return self
+            .cell
+            .get_or_init(|| alloc::boxed::Box::new((self.init)()));
+    }
+
+    /// get returns the model if it has been built.
+    #[cfg(test)]
+    pub(crate) fn get(&self) -> Option<&crate::Model> {
+        self.cell.get()
+    }
+}
+
+impl Deref for LazyModel {
+    type Target = crate::Model;
+
+    fn deref(&self) -> &crate::Model {
+        self.force()
+    }
+}
+
+/// ModelFn is a function returning a bundled model.
+pub(crate) type ModelFn = fn() -> &'static crate::Model;
+
+/// MODELS is the BCP 47 language tag and the function returning each bundled trained model.
+///
+/// `supported_languages`, `preload_all` and `crate::Model::for_language` are derived from it, so a
+/// new model is added to this table only.
+pub(crate) const MODELS: [(&str, ModelFn); 4] = [
+    ("ja", default_japanese_model),
+    ("th", default_thai_model),
+    ("zh-Hans", default_simplified_chinese_model),
+    ("zh-Hant", default_traditional_chinese_model),
+];
+
+/// LANGUAGES is the BCP 47 language tags of the bundled trained models.
+const LANGUAGES: [&str; MODELS.len()] = {
+    let mut tags = [""; MODELS.len()];
+    let mut i = 0;
+    while i < MODELS.len() {
+        tags[i] = MODELS[i].0;
+        i += 1;
+    }
+    tags
+};
+
+/// supported_languages returns the BCP 47 language tags of the bundled trained models.
+///
+/// Each tag is accepted by [`crate::Model::for_language`].
+///
+/// # Examples
+///
+/// ```rust
+/// for tag in budoux::models::supported_languages() {
+///     assert!(budoux::Model::for_language(tag).is_some());
+/// }
+/// ```
+pub fn supported_languages() -> &'static [&'static str] {
+    &LANGUAGES
+}
+
 /// default_japanese_model returns trained machine learning model for japanese.
 pub fn default_japanese_model() -> &'static crate::Model {
     &ja::MODEL
@@ -20,6 +113,14 @@ pub fn default_thai_model() -> &'static crate::Model {
     &th::MODEL
 }
 
+/// default_latin_model returns hand-authored fallback model for languages written with spaces.
+///
+/// It breaks after spaces, tabs, slashes and dashes, and never before a space, so that it can be
+/// used for languages without a trained model. Text without these characters is never split.
+pub fn default_latin_model() -> &'static crate::Model {
+    &latin::MODEL
+}
+
 /// default_simplified_chinese_model returns trained machine learning model for simplified chinese.
 pub fn default_simplified_chinese_model() -> &'static crate::Model {
     &zh_hans::MODEL
@@ -30,8 +131,52 @@ pub fn default_traditional_chinese_model() -> &'static crate::Model {
     &zh_hant::MODEL
 }
 
+/// preload_all initializes every bundled model, so that the first parse does not pay for it.
+///
+/// The bundled models are initialized on first use, which can be forced at startup with this
+/// function, e.g. before a server starts accepting requests.
+///
+/// # Examples
+///
+/// ```rust
+/// budoux::models::preload_all();
+/// ```
+pub fn preload_all() {
+    for (_, model) in MODELS {
+        model();
+    }
+    preload_latin();
+}
+
+/// preload_japanese initializes the model returned by `default_japanese_model`.
+pub fn preload_japanese() {
+    ja::MODEL.force();
+}
+
+/// preload_thai initializes the model returned by `default_thai_model`.
+pub fn preload_thai() {
+    th::MODEL.force();
+}
+
+/// preload_latin initializes the model returned by `default_latin_model`.
+pub fn preload_latin() {
+    latin::MODEL.force();
+}
+
+/// preload_simplified_chinese initializes the model returned by `default_simplified_chinese_model`.
+pub fn preload_simplified_chinese() {
+    zh_hans::MODEL.force();
+}
+
+/// preload_traditional_chinese initializes the model returned by `default_traditional_chinese_model`.
+pub fn preload_traditional_chinese() {
+    zh_hant::MODEL.force();
+}
+
 #[cfg(test)]
 mod tests {
+    use alloc::{vec, vec::Vec};
+
     #[test]
     fn test_multiple_ref() {
         let m1 = super::default_japanese_model();
@@ -40,6 +185,104 @@ mod tests {
         assert_eq!(m1, m2);
     }
 
+    #[test]
+    fn test_feature_groups() {
+        // bundled models have no unicode block (UB*) features, so parse scores every feature group.
+        for m in [
+            super::default_japanese_model(),
+            super::default_thai_model(),
+            super::default_simplified_chinese_model(),
+            super::default_traditional_chinese_model(),
+        ] {
+            assert_eq!(m.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_generated_models() {
+        // the build script generates the same models as the json.
+        for (m, json) in [
+            (
+                super::default_japanese_model(),
+                include_str!("../../resources/ja.json"),
+            ),
+            (
+                super::default_latin_model(),
+                include_str!("../../resources/latin.json"),
+            ),
+            (
+                super::default_thai_model(),
+                include_str!("../../resources/th.json"),
+            ),
+            (
+                super::default_simplified_chinese_model(),
+                include_str!("../../resources/zh-hans.json"),
+            ),
+            (
+                super::default_traditional_chinese_model(),
+                include_str!("../../resources/zh-hant.json"),
+            ),
+        ] {
+            assert_eq!(m, &crate::Model::from_str(json).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_preload() {
+        super::preload_all();
+        for (m, lazy) in [
+            (super::default_japanese_model(), &super::ja::MODEL),
+            (super::default_thai_model(), &super::th::MODEL),
+            (super::default_latin_model(), &super::latin::MODEL),
+            (
+                super::default_simplified_chinese_model(),
+                &super::zh_hans::MODEL,
+            ),
+            (
+                super::default_traditional_chinese_model(),
+                &super::zh_hant::MODEL,
+            ),
+        ] {
+            let preloaded = lazy.get().unwrap();
+            assert!(core::ptr::eq(m, preloaded));
+        }
+    }
+
+    #[test]
+    fn test_supported_languages() {
+        let models = super::supported_languages()
+            .iter()
+            .map(|tag| crate::Model::for_language(tag).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            models,
+            vec![
+                super::default_japanese_model(),
+                super::default_thai_model(),
+                super::default_simplified_chinese_model(),
+                super::default_traditional_chinese_model(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_latin_model() {
+        let m = super::default_latin_model();
+
+        assert_eq!(m.validate(), Ok(()));
+        assert_eq!(m.parse(""), Vec::<&str>::new());
+        assert_eq!(
+            m.parse("Xin chào thế giới!"),
+            vec!["Xin ", "chào ", "thế ", "giới!"]
+        );
+        assert_eq!(
+            m.parse("well-known  and/or\tnot"),
+            vec!["well-", "known  ", "and/", "or\t", "not"]
+        );
+        assert_eq!(m.parse("これはテストです。"), vec!["これはテストです。"]);
+    }
+
     #[test]
     fn test_multiple_ref_zh_hans() {
         let m1 = super::default_simplified_chinese_model();
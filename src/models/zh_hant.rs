@@ -1,5 +1,7 @@
-use once_cell::sync::Lazy;
+use super::LazyModel;
 
 /// MODEL reference to trained machine learning model.
-pub static MODEL: Lazy<crate::Model> =
-    Lazy::new(|| crate::Model::from_str(include_str!("../../resources/zh-hant.json")).unwrap());
+pub static MODEL: LazyModel = LazyModel::new(|| crate::Model::from_features(FEATURES));
+
+/// FEATURES is the feature map generated from `resources/zh-hant.json` by the build script.
+static FEATURES: &[(&str, &[(&str, i32)])] = include!(concat!(env!("OUT_DIR"), "/zh_hant.rs"));
@@ -0,0 +1,200 @@
+use alloc::borrow::Cow;
+
+use crate::{
+    DEFAULT_CLOSING_PUNCTUATION, DEFAULT_KINSOKU_NOT_AT_END, DEFAULT_KINSOKU_NOT_AT_START,
+    DEFAULT_PUNCTUATION, DEFAULT_THRESHOLD,
+};
+
+/// ParseOptions is options of `Model::parse_with_options`.
+///
+/// # Examples
+///
+/// ```rust
+/// let options = budoux::ParseOptions {
+///     hard_break_on_newline: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// threshold is the score a boundary must reach to insert a break. Defaults to `DEFAULT_THRESHOLD`.
+    pub threshold: i32,
+    /// hard_break_on_newline always breaks after a newline (`\n`, `\r\n` or `\r`) regardless of the score.
+    /// The newline is kept at the end of the preceding chunk.
+    pub hard_break_on_newline: bool,
+    /// max_chunk_chars caps the number of characters in a chunk. A break is forced when a chunk
+    /// reaches the cap, even if the score is below the threshold. `Some(0)` is treated as `Some(1)`.
+    /// With `hard_break_on_newline`, a newline at the cap is kept at the end of the chunk, which
+    /// then exceeds the cap.
+    pub max_chunk_chars: Option<usize>,
+    /// min_chunk_chars merges a chunk with fewer characters than the minimum into the previous chunk,
+    /// or into the next chunk if it is the first one. Input shorter than the minimum is a single
+    /// chunk. Breaks forced by `hard_break_on_newline` and `max_chunk_chars` are kept.
+    pub min_chunk_chars: Option<usize>,
+    /// split_on_whitespace always breaks at ASCII whitespace and drops it from the chunks,
+    /// so that no chunk contains whitespace and no chunk is empty.
+    pub split_on_whitespace: bool,
+    /// normalize case folds characters before looking up features, so that `Python` and `python`
+    /// are split alike. Only the lookup is affected, and the chunks are still slices of the input.
+    pub normalize: bool,
+    /// preserve_indent excludes the leading spaces and tabs of each line from the feature lookup,
+    /// and reattaches them to the first chunk of the line. No break is inserted inside or right
+    /// after the indent, unless forced by other options.
+    pub preserve_indent: bool,
+    /// skip_blank returns no chunks if input consists only of whitespace and control characters.
+    /// Otherwise such input is returned as is, e.g. `"\n"` is parsed into `["\n"]`.
+    pub skip_blank: bool,
+    /// detach_punctuation always breaks before the characters in `punctuation`, so that they are
+    /// separate chunks from the preceding word.
+    pub detach_punctuation: bool,
+    /// punctuation is the characters detached by `detach_punctuation`. Defaults to `DEFAULT_PUNCTUATION`.
+    pub punctuation: Cow<'static, str>,
+    /// no_break_before_closing never breaks before the characters in `closing_punctuation`, so that
+    /// no chunk starts with them. It takes precedence over `detach_punctuation`.
+    pub no_break_before_closing: bool,
+    /// closing_punctuation is the characters kept by `no_break_before_closing`.
+    /// Defaults to `DEFAULT_CLOSING_PUNCTUATION`.
+    pub closing_punctuation: Cow<'static, str>,
+    /// keep_latin_words_whole never breaks between consecutive ASCII alphanumeric characters,
+    /// so that words such as `JavaScript` or `HTML5` are never split. Breaks forced by
+    /// `max_chunk_chars` are kept.
+    pub keep_latin_words_whole: bool,
+    /// kinsoku applies line break prohibition rules (kinsoku shori) after the boundaries are decided.
+    /// A boundary is removed if the following chunk starts with a character in `kinsoku_not_at_start`
+    /// or the preceding chunk ends with a character in `kinsoku_not_at_end`. Boundaries forced by
+    /// `hard_break_on_newline` and `max_chunk_chars` are kept.
+    pub kinsoku: bool,
+    /// kinsoku_not_at_start is the characters which must not start a chunk.
+    /// Defaults to `DEFAULT_KINSOKU_NOT_AT_START`.
+    pub kinsoku_not_at_start: Cow<'static, str>,
+    /// kinsoku_not_at_end is the characters which must not end a chunk.
+    /// Defaults to `DEFAULT_KINSOKU_NOT_AT_END`.
+    pub kinsoku_not_at_end: Cow<'static, str>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_THRESHOLD,
+            hard_break_on_newline: false,
+            max_chunk_chars: None,
+            min_chunk_chars: None,
+            split_on_whitespace: false,
+            normalize: false,
+            preserve_indent: false,
+            skip_blank: false,
+            detach_punctuation: false,
+            punctuation: Cow::Borrowed(DEFAULT_PUNCTUATION),
+            no_break_before_closing: false,
+            closing_punctuation: Cow::Borrowed(DEFAULT_CLOSING_PUNCTUATION),
+            keep_latin_words_whole: false,
+            kinsoku: false,
+            kinsoku_not_at_start: Cow::Borrowed(DEFAULT_KINSOKU_NOT_AT_START),
+            kinsoku_not_at_end: Cow::Borrowed(DEFAULT_KINSOKU_NOT_AT_END),
+        }
+    }
+}
+
+impl ParseOptions {
+    /// is_indent returns whether c is counted as indent by `preserve_indent`.
+    pub(crate) fn is_indent(c: char) -> bool {
+        c == ' ' || c == '\t'
+    }
+
+    /// forced_break returns whether the boundary before `chars[i]` is decided regardless of the score.
+    ///
+    /// `start` is the index of the first character of the current chunk.
+    pub(crate) fn forced_break(
+        &self,
+        chars: &[(usize, char)],
+        start: usize,
+        i: usize,
+    ) -> Option<bool> {
+        if self.split_on_whitespace
+            && (chars[i].1.is_ascii_whitespace() || chars[i - 1].1.is_ascii_whitespace())
+        {
+            return Some(true);
+        }
+        if self.hard_break_on_newline {
+            let is_newline = |c: char| c == '\n' || c == '\r';
+            if is_newline(chars[i].1) {
+                return Some(false);
+            }
+            if is_newline(chars[i - 1].1) {
+                return Some(true);
+            }
+        }
+        if let Some(max) = self.max_chunk_chars {
+            if i - start >= max {
+                return Some(true);
+            }
+        }
+        if self.keep_latin_words_whole
+            && chars[i].1.is_ascii_alphanumeric()
+            && chars[i - 1].1.is_ascii_alphanumeric()
+        {
+            return Some(false);
+        }
+        if self.no_break_before_closing && self.closing_punctuation.contains(chars[i].1) {
+            return Some(false);
+        }
+        if self.detach_punctuation && self.punctuation.contains(chars[i].1) {
+            return Some(true);
+        }
+        None
+    }
+
+    /// kinsoku_merges returns whether the boundary between the adjacent chunks is removed by kinsoku.
+    pub(crate) fn kinsoku_merges(&self, before: &str, after: &str) -> bool {
+        let (Some(last), Some(first)) = (before.chars().next_back(), after.chars().next()) else {
+            return false;
+        };
+        self.kinsoku
+            && (self.kinsoku_not_at_start.contains(first) || self.kinsoku_not_at_end.contains(last))
+            && self.can_merge(before, after)
+    }
+
+    /// min_chunk_merges returns whether the adjacent chunks are merged by `min_chunk_chars`.
+    pub(crate) fn min_chunk_merges(&self, before: &str, after: &str) -> bool {
+        match self.min_chunk_chars {
+            Some(min) => {
+                (before.chars().count() < min || after.chars().count() < min)
+                    && self.can_merge(before, after)
+            }
+            None => false,
+        }
+    }
+
+    /// can_merge returns whether merging the adjacent chunks keeps the forced breaks of
+    /// `hard_break_on_newline` and `max_chunk_chars`.
+    fn can_merge(&self, before: &str, after: &str) -> bool {
+        if self.hard_break_on_newline && before.ends_with(['\n', '\r']) {
+            return false;
+        }
+        match self.max_chunk_chars {
+            Some(max) => before.chars().count() + after.chars().count() <= max,
+            None => true,
+        }
+    }
+
+    /// is_skipped returns whether the whole input is removed from the result.
+    pub(crate) fn is_skipped(&self, input: &str) -> bool {
+        self.skip_blank && input.chars().all(|c| c.is_whitespace() || c.is_control())
+    }
+
+    /// is_dropped returns whether the chunk is removed from the result.
+    pub(crate) fn is_dropped(&self, chunk: &str) -> bool {
+        self.split_on_whitespace && chunk.bytes().all(|c| c.is_ascii_whitespace())
+    }
+}
+
+/// fold_case returns the lowercase of c if it is a single character, otherwise c itself.
+///
+/// Mapping each character to exactly one character keeps character indices intact.
+pub(crate) fn fold_case(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        _ => c,
+    }
+}
@@ -0,0 +1,267 @@
+use alloc::{string::String, vec, vec::Vec};
+use core::ops::Range;
+
+use crate::{options::fold_case, Model, ParseOptions};
+
+/// Parser is sentence parser which owns reusable scratch buffers.
+///
+/// Repeated calls reuse the buffers, so allocations are amortized across calls.
+///
+/// # Examples
+///
+/// ```rust
+/// let model = budoux::models::default_japanese_model();
+/// let mut parser = model.parser();
+///
+/// assert_eq!(parser.parse("これはテストです。"), vec!["これは", "テストです。"]);
+/// assert_eq!(parser.parse("水と油"), vec!["水と", "油"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Parser<'m> {
+    model: &'m Model,
+    chars: Vec<(usize, char)>,
+    chunks: Vec<Range<usize>>,
+    scores: Vec<i32>,
+    normalized: String,
+    kept: Vec<usize>,
+    kept_scores: Vec<i32>,
+}
+
+impl<'m> Parser<'m> {
+    /// Creates a new `Parser` instance for the model.
+    pub fn new(model: &'m Model) -> Self {
+        Self {
+            model,
+            chars: vec![],
+            chunks: vec![],
+            scores: vec![],
+            normalized: String::new(),
+            kept: vec![],
+            kept_scores: vec![],
+        }
+    }
+
+    /// model returns the model used by the parser.
+    pub fn model(&self) -> &'m Model {
+        self.model
+    }
+
+    /// parse returns splitted string slice from input.
+    ///
+    /// * `input` - input sentences.
+    pub fn parse<'i>(&mut self, input: &'i str) -> Vec<&'i str> {
+        let mut out = vec![];
+        self.parse_into(input, &mut out);
+        out
+    }
+
+    /// parse_into stores splitted string slice from input into `out`.
+    ///
+    /// `out` is cleared before parsing.
+    ///
+    /// * `input` - input sentences.
+    /// * `out` - output buffer.
+    pub fn parse_into<'i>(&mut self, input: &'i str, out: &mut Vec<&'i str>) {
+        self.parse_with_options_into(input, &ParseOptions::default(), out);
+    }
+
+    /// parse_with_options returns splitted string slice from input using the given options.
+    ///
+    /// * `input` - input sentences.
+    /// * `options` - parse options.
+    pub fn parse_with_options<'i>(
+        &mut self,
+        input: &'i str,
+        options: &ParseOptions,
+    ) -> Vec<&'i str> {
+        let mut out = vec![];
+        self.parse_with_options_into(input, options, &mut out);
+        out
+    }
+
+    /// parse_with_options_into stores splitted string slice from input into `out` using the given options.
+    ///
+    /// `out` is cleared before parsing.
+    ///
+    /// * `input` - input sentences.
+    /// * `options` - parse options.
+    /// * `out` - output buffer.
+    pub fn parse_with_options_into<'i>(
+        &mut self,
+        input: &'i str,
+        options: &ParseOptions,
+        out: &mut Vec<&'i str>,
+    ) {
+        self.split_ranges(input, options);
+        out.clear();
+        out.extend(self.chunks.iter().map(|r| &input[r.clone()]));
+    }
+
+    /// parse_ranges returns splitted string slice from input paired with its byte range in input.
+    ///
+    /// * `input` - input sentences.
+    pub fn parse_ranges<'i>(&mut self, input: &'i str) -> Vec<(Range<usize>, &'i str)> {
+        self.split_ranges(input, &ParseOptions::default());
+        self.chunks
+            .iter()
+            .map(|r| (r.clone(), &input[r.clone()]))
+            .collect()
+    }
+
+    /// split_ranges stores byte ranges of the chunks of input into the scratch buffer.
+    fn split_ranges(&mut self, input: &str, options: &ParseOptions) {
+        self.chars.clear();
+        self.chars.extend(input.char_indices());
+        self.chunks.clear();
+        if options.is_skipped(input) {
+            return;
+        }
+        // features are looked up in text, whose characters are those of input at kept.
+        let text = if options.normalize || options.preserve_indent {
+            self.normalized.clear();
+            self.kept.clear();
+            let mut line_start = true;
+            for (i, &(_, c)) in self.chars.iter().enumerate() {
+                if options.preserve_indent && line_start && ParseOptions::is_indent(c) {
+                    continue;
+                }
+                line_start = c == '\n';
+                self.kept.push(i);
+                self.normalized
+                    .push(if options.normalize { fold_case(c) } else { c });
+            }
+            self.normalized.as_str()
+        } else {
+            input
+        };
+        // scores are computed in bulk before chunks are emitted, unless they are known to be below
+        // the threshold.
+        self.scores.clear();
+        if self.model.never_breaks_ascii(text, options.threshold) {
+            self.scores
+                .resize(self.chars.len().saturating_sub(1), i32::MIN);
+        } else if options.preserve_indent {
+            // no break is inserted inside or right after an indent, and the score of the boundary
+            // before the first kept character of a line moves to the start of the indent.
+            self.scores
+                .resize(self.chars.len().saturating_sub(1), i32::MIN);
+            self.kept_scores.clear();
+            self.kept_scores
+                .resize(self.kept.len().saturating_sub(1), 0);
+            self.model.fill_scores(text, &mut self.kept_scores);
+            for (pair, &score) in self.kept.windows(2).zip(&self.kept_scores) {
+                let i = if pair[1] > pair[0] + 1 {
+                    pair[0] + 1
+                } else {
+                    pair[1]
+                };
+                self.scores[i - 1] = score;
+            }
+        } else {
+            self.scores.resize(self.chars.len().saturating_sub(1), 0);
+            self.model.fill_scores(text, &mut self.scores);
+        }
+
+        let mut start = 0;
+        let mut start_index = 0;
+        for i in 1..self.chars.len() {
+            let is_break = match options.forced_break(&self.chars, start_index, i) {
+                Some(is_break) => is_break,
+                None => self.scores[i - 1] >= options.threshold,
+            };
+            if is_break {
+                let end = self.chars[i].0;
+                if !options.is_dropped(&input[start..end]) {
+                    self.chunks.push(start..end);
+                }
+                start = end;
+                start_index = i;
+            }
+        }
+        if !input.is_empty() && !options.is_dropped(&input[start..]) {
+            self.chunks.push(start..input.len());
+        }
+        if options.kinsoku {
+            self.merge_chunks(input, |before, after| options.kinsoku_merges(before, after));
+        }
+        if options.min_chunk_chars.is_some() {
+            self.merge_chunks(input, |before, after| {
+                options.min_chunk_merges(before, after)
+            });
+        }
+    }
+
+    /// merge_chunks merges adjacent chunks in place where `merges` returns true for them.
+    fn merge_chunks(&mut self, input: &str, merges: impl Fn(&str, &str) -> bool) {
+        let mut len = 0;
+        for i in 0..self.chunks.len() {
+            let chunk = self.chunks[i].clone();
+            if len > 0 {
+                let last = &mut self.chunks[len - 1];
+                if last.end == chunk.start && merges(&input[last.clone()], &input[chunk.clone()]) {
+                    last.end = chunk.end;
+                    continue;
+                }
+            }
+            self.chunks[len] = chunk;
+            len += 1;
+        }
+        self.chunks.truncate(len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn test_parse() {
+        let m = crate::models::default_japanese_model();
+        let mut p = m.parser();
+
+        for input in [
+            "",
+            "日本語",
+            "水と油",
+            "日本語の文章において語の区切りに空白を挟んで記述すること",
+            "これはテストです。\n今日は晴天です。",
+            "",
+        ] {
+            assert_eq!(p.parse(input), m.parse(input));
+        }
+    }
+
+    #[test]
+    fn test_parse_into() {
+        let m = crate::models::default_simplified_chinese_model();
+        let mut p = m.parser();
+        let mut out = vec![];
+
+        p.parse_into("今天是晴天。", &mut out);
+        assert_eq!(out, vec!["今天", "是", "晴天。"]);
+        p.parse_into("", &mut out);
+        assert_eq!(out, Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_parse_with_options_normalize() {
+        let m = crate::Model::from_str(r#"{"UW4": {"p": 1000}}"#).unwrap();
+        let mut p = m.parser();
+        let options = crate::ParseOptions {
+            normalize: true,
+            ..Default::default()
+        };
+
+        assert_eq!(p.parse("ipython iPython"), vec!["i", "python iPython"]);
+        assert_eq!(
+            p.parse_with_options("ipython iPython", &options),
+            vec!["i", "python i", "Python"]
+        );
+        assert_eq!(p.parse_with_options("", &options), Vec::<&str>::new());
+
+        let m = crate::Model::from_str(r#"{"UW4": {"i": 1000}}"#).unwrap();
+        let mut p = m.parser();
+        // U+0130 lowercases to two characters, so it is kept as is.
+        assert_eq!(p.parse_with_options("aIİi", &options), vec!["a", "Iİ", "i"]);
+    }
+}
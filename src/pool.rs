@@ -0,0 +1,146 @@
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Mutex,
+};
+
+use crate::{Model, Parser};
+
+/// ParserPool is thread-safe pool of `Parser`s, so that threads parsing concurrently reuse the
+/// scratch buffers of each other instead of allocating their own.
+///
+/// The model is shared read-only by every parser in the pool. The lock is only held while a
+/// parser is taken from or returned to the pool, not while parsing.
+///
+/// # Examples
+///
+/// ```rust
+/// let model = budoux::models::default_japanese_model();
+/// let pool = budoux::ParserPool::new(model);
+///
+/// std::thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| {
+///             let mut parser = pool.get();
+///             assert_eq!(parser.parse("これはテストです。"), vec!["これは", "テストです。"]);
+///         });
+///     }
+/// });
+/// ```
+#[derive(Debug)]
+pub struct ParserPool<'m> {
+    model: &'m Model,
+    parsers: Mutex<Vec<Parser<'m>>>,
+}
+
+impl<'m> ParserPool<'m> {
+    /// Creates a new empty `ParserPool` instance for the model.
+    pub fn new(model: &'m Model) -> Self {
+        Self {
+            model,
+            parsers: Mutex::new(vec![]),
+        }
+    }
+
+    /// model returns the model used by the parsers.
+    pub fn model(&self) -> &'m Model {
+        self.model
+    }
+
+    /// get takes an idle parser from the pool, or creates a new one if every parser is in use.
+    ///
+    /// The parser is returned to the pool when the `PooledParser` is dropped.
+    pub fn get(&self) -> PooledParser<'_, 'm> {
+        let parser = self.lock().pop().unwrap_or_else(|| Parser::new(self.model));
+        PooledParser {
+            pool: self,
+            parser: Some(parser),
+        }
+    }
+
+    /// idle returns the number of parsers waiting in the pool.
+    pub fn idle(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Parser<'m>>> {
+        // the pool is never left inconsistent, so a poisoned lock is still usable.
+        self.parsers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// PooledParser is `Parser` borrowed from `ParserPool`, which is returned to the pool on drop.
+#[derive(Debug)]
+pub struct PooledParser<'p, 'm> {
+    pool: &'p ParserPool<'m>,
+    parser: Option<Parser<'m>>,
+}
+
+impl<'m> Deref for PooledParser<'_, 'm> {
+    type Target = Parser<'m>;
+
+    fn deref(&self) -> &Self::Target {
+        self.parser.as_ref().expect("parser is present until drop")
+    }
+}
+
+impl DerefMut for PooledParser<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.parser.as_mut().expect("parser is present until drop")
+    }
+}
+
+impl Drop for PooledParser<'_, '_> {
+    fn drop(&mut self) {
+        if let Some(parser) = self.parser.take() {
+            self.pool.lock().push(parser);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParserPool;
+
+    #[test]
+    fn test_get() {
+        let m = crate::models::default_japanese_model();
+        let pool = ParserPool::new(m);
+
+        assert_eq!(pool.idle(), 0);
+        {
+            let mut p1 = pool.get();
+            let mut p2 = pool.get();
+            assert_eq!(p1.parse("水と油"), m.parse("水と油"));
+            assert_eq!(
+                p2.parse("これはテストです。"),
+                m.parse("これはテストです。")
+            );
+            assert_eq!(pool.idle(), 0);
+        }
+        assert_eq!(pool.idle(), 2);
+
+        let mut p = pool.get();
+        assert_eq!(pool.idle(), 1);
+        assert_eq!(p.parse("日本語"), m.parse("日本語"));
+        drop(p);
+        assert_eq!(pool.idle(), 2);
+    }
+
+    #[test]
+    fn test_get_threads() {
+        let m = crate::models::default_japanese_model();
+        let pool = ParserPool::new(m);
+        let input = "日本語の文章において語の区切りに空白を挟んで記述すること";
+
+        std::thread::scope(|s| {
+            for _ in 0..8 {
+                s.spawn(|| {
+                    for _ in 0..16 {
+                        assert_eq!(pool.get().parse(input), m.parse(input));
+                    }
+                });
+            }
+        });
+        assert!((1..=8).contains(&pool.idle()));
+    }
+}
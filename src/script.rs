@@ -0,0 +1,249 @@
+use alloc::vec::Vec;
+
+use crate::Model;
+
+/// Script is the writing system of a run of text returned by `script_runs`.
+///
+/// Hiragana and katakana are a single `Kana` script. Han is shared by chinese and japanese, so
+/// a script run alone cannot tell them apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script {
+    /// Han is CJK ideographs, including `々` and `〇`.
+    Han,
+    /// Kana is hiragana and katakana, including the prolonged sound mark `ー`.
+    Kana,
+    /// Hangul is korean hangul syllables and jamo.
+    Hangul,
+    /// Thai is the thai script.
+    Thai,
+    /// Latin is latin letters, including fullwidth ones.
+    Latin,
+    /// Common is characters shared by every script, such as digits, punctuation and whitespace.
+    Common,
+}
+
+impl Script {
+    /// of returns the script of c.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use budoux::Script;
+    ///
+    /// assert_eq!(Script::of('漢'), Script::Han);
+    /// assert_eq!(Script::of('か'), Script::Kana);
+    /// assert_eq!(Script::of('。'), Script::Common);
+    /// ```
+    pub fn of(c: char) -> Script {
+        match c {
+            '\u{3005}' | '\u{3007}' | '\u{3400}'..='\u{4dbf}' | '\u{4e00}'..='\u{9fff}' => {
+                Script::Han
+            }
+            '\u{f900}'..='\u{faff}' | '\u{20000}'..='\u{3ffff}' => Script::Han,
+            '\u{3041}'..='\u{309f}' | '\u{30a0}'..='\u{30ff}' | '\u{31f0}'..='\u{31ff}' => {
+                Script::Kana
+            }
+            '\u{ff66}'..='\u{ff9f}' => Script::Kana,
+            '\u{1100}'..='\u{11ff}' | '\u{3130}'..='\u{318f}' | '\u{ac00}'..='\u{d7af}' => {
+                Script::Hangul
+            }
+            '\u{0e00}'..='\u{0e7f}' => Script::Thai,
+            'A'..='Z' | 'a'..='z' | '\u{ff21}'..='\u{ff3a}' | '\u{ff41}'..='\u{ff5a}' => {
+                Script::Latin
+            }
+            '\u{c0}'..='\u{24f}' | '\u{1e00}'..='\u{1eff}' if c.is_alphabetic() => Script::Latin,
+            _ => Script::Common,
+        }
+    }
+}
+
+/// script_runs splits input into runs of the same script.
+///
+/// `Script::Common` characters belong to the preceding run, or to the following run at the start
+/// of input, so that punctuation and spaces never make a run on their own. Input consisting only
+/// of common characters is a single `Script::Common` run.
+///
+/// * `input` - input sentences.
+///
+/// # Examples
+///
+/// ```rust
+/// use budoux::Script;
+///
+/// assert_eq!(
+///     budoux::script_runs("「สวัสดี」と言った。"),
+///     vec![(Script::Thai, "「สวัสดี」"), (Script::Kana, "と"), (Script::Han, "言"), (Script::Kana, "った。")]
+/// );
+/// ```
+pub fn script_runs(input: &str) -> Vec<(Script, &str)> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    let mut script = Script::Common;
+    for (i, c) in input.char_indices() {
+        let next = Script::of(c);
+        if next == Script::Common || next == script {
+            continue;
+        }
+        if script != Script::Common {
+            runs.push((script, &input[start..i]));
+            start = i;
+        }
+        script = next;
+    }
+    if !input.is_empty() {
+        runs.push((script, &input[start..]));
+    }
+    runs
+}
+
+impl Model {
+    /// parse_multilingual returns splitted string slice from input, parsing each script run with
+    /// the model of its script in `models`, or with the model itself for scripts not in `models`.
+    ///
+    /// Adjacent runs parsed with the same model are parsed together, and a break is always inserted
+    /// where the model changes. As japanese is written with han and kana, mapping `Script::Han` to a
+    /// chinese model also splits the han in japanese text.
+    ///
+    /// * `input` - input sentences.
+    /// * `models` - models of the scripts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use budoux::{models, Script};
+    ///
+    /// let ja = models::default_japanese_model();
+    /// let th = models::default_thai_model();
+    /// let input = "これはテストです。สวัสดีครับ";
+    ///
+    /// assert_eq!(
+    ///     ja.parse_multilingual(input, &[(Script::Thai, th)]),
+    ///     [ja.parse("これはテストです。"), th.parse("สวัสดีครับ")].concat()
+    /// );
+    /// ```
+    pub fn parse_multilingual<'i>(
+        &self,
+        input: &'i str,
+        models: &[(Script, &Model)],
+    ) -> Vec<&'i str> {
+        let model_of = |script: Script| {
+            models
+                .iter()
+                .find(|&&(s, _)| s == script)
+                .map_or(self, |&(_, model)| model)
+        };
+        let mut words = Vec::new();
+        let mut start = 0;
+        let mut offset = 0;
+        let mut current: Option<&Model> = None;
+        for (script, run) in script_runs(input) {
+            let model = model_of(script);
+            match current {
+                Some(prev) if !core::ptr::eq(prev, model) => {
+                    words.extend(prev.parse(&input[start..offset]));
+                    start = offset;
+                }
+                _ => {}
+            }
+            current = Some(model);
+            offset += run.len();
+        }
+        if let Some(model) = current {
+            words.extend(model.parse(&input[start..]));
+        }
+        words
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{script_runs, Script};
+    use alloc::{string::String, vec, vec::Vec};
+
+    #[test]
+    fn test_script_of() {
+        for (c, script) in [
+            ('日', Script::Han),
+            ('々', Script::Han),
+            ('𠀋', Script::Han),
+            ('あ', Script::Kana),
+            ('ア', Script::Kana),
+            ('ー', Script::Kana),
+            ('ｱ', Script::Kana),
+            ('한', Script::Hangul),
+            ('ก', Script::Thai),
+            ('a', Script::Latin),
+            ('é', Script::Latin),
+            ('ệ', Script::Latin),
+            ('Ａ', Script::Latin),
+            ('1', Script::Common),
+            (' ', Script::Common),
+            ('。', Script::Common),
+            ('×', Script::Common),
+            ('😀', Script::Common),
+        ] {
+            assert_eq!(Script::of(c), script, "{c:?}");
+        }
+    }
+
+    #[test]
+    fn test_script_runs() {
+        assert_eq!(script_runs(""), vec![]);
+        assert_eq!(script_runs("123 。"), vec![(Script::Common, "123 。")]);
+        assert_eq!(
+            script_runs("「BudouX」は2023年"),
+            vec![
+                (Script::Latin, "「BudouX」"),
+                (Script::Kana, "は2023"),
+                (Script::Han, "年"),
+            ]
+        );
+        let input = "日本語、한국어 and ไทย.";
+        assert_eq!(
+            script_runs(input),
+            vec![
+                (Script::Han, "日本語、"),
+                (Script::Hangul, "한국어 "),
+                (Script::Latin, "and "),
+                (Script::Thai, "ไทย."),
+            ]
+        );
+        assert_eq!(
+            script_runs(input)
+                .iter()
+                .map(|&(_, run)| run)
+                .collect::<String>(),
+            input
+        );
+    }
+
+    #[test]
+    fn test_parse_multilingual() {
+        let ja = crate::models::default_japanese_model();
+        let th = crate::models::default_thai_model();
+        let zh = crate::models::default_simplified_chinese_model();
+
+        assert_eq!(ja.parse_multilingual("", &[]), Vec::<&str>::new());
+        for input in ["これはテストです。", "日本語の文章において"] {
+            assert_eq!(ja.parse_multilingual(input, &[]), ja.parse(input));
+            assert_eq!(
+                ja.parse_multilingual(input, &[(Script::Thai, th)]),
+                ja.parse(input)
+            );
+        }
+
+        let input = "今日は晴天です。สวัสดีครับ";
+        let words = ja.parse_multilingual(input, &[(Script::Thai, th)]);
+        assert_eq!(words.concat(), input);
+        assert_eq!(
+            words,
+            [ja.parse("今日は晴天です。"), th.parse("สวัสดีครับ")].concat()
+        );
+
+        // a model mapped to several scripts parses their runs together.
+        assert_eq!(
+            zh.parse_multilingual("我们的使命", &[(Script::Han, zh), (Script::Kana, zh)]),
+            zh.parse("我们的使命")
+        );
+    }
+}
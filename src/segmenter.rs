@@ -0,0 +1,70 @@
+use alloc::vec::Vec;
+
+use crate::Model;
+
+/// Segmenter is a backend splitting sentences into chunks, so that applications can be generic
+/// over `Model` and other segmenters, or pick one at runtime as `&dyn Segmenter`.
+///
+/// # Examples
+///
+/// ```rust
+/// use budoux::Segmenter;
+///
+/// struct Whitespace;
+///
+/// impl Segmenter for Whitespace {
+///     fn segment<'i>(&self, input: &'i str) -> Vec<&'i str> {
+///         input.split_inclusive(' ').collect()
+///     }
+/// }
+///
+/// let segmenters: [&dyn Segmenter; 2] = [budoux::models::default_japanese_model(), &Whitespace];
+///
+/// assert_eq!(segmenters[0].segment("これはテストです。"), vec!["これは", "テストです。"]);
+/// assert_eq!(segmenters[1].segment("a b"), vec!["a ", "b"]);
+/// ```
+pub trait Segmenter {
+    /// segment returns splitted string slice from input.
+    ///
+    /// * `input` - input sentences.
+    fn segment<'i>(&self, input: &'i str) -> Vec<&'i str>;
+}
+
+impl Segmenter for Model {
+    fn segment<'i>(&self, input: &'i str) -> Vec<&'i str> {
+        self.parse(input)
+    }
+}
+
+impl<S: Segmenter + ?Sized> Segmenter for &S {
+    fn segment<'i>(&self, input: &'i str) -> Vec<&'i str> {
+        (**self).segment(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Segmenter;
+    use alloc::vec::Vec;
+
+    fn segment_all<'i>(segmenter: impl Segmenter, inputs: &[&'i str]) -> Vec<Vec<&'i str>> {
+        inputs
+            .iter()
+            .map(|input| segmenter.segment(input))
+            .collect()
+    }
+
+    #[test]
+    fn test_segment() {
+        let m = crate::models::default_japanese_model();
+        let inputs = ["", "日本語", "これはテストです。今日は晴天です。"];
+        let expected = inputs
+            .iter()
+            .map(|input| m.parse(input))
+            .collect::<Vec<_>>();
+
+        assert_eq!(segment_all(m, &inputs), expected);
+        let dynamic: &dyn Segmenter = m;
+        assert_eq!(segment_all(dynamic, &inputs), expected);
+    }
+}
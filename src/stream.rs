@@ -0,0 +1,152 @@
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead},
+};
+
+use crate::{Model, DEFAULT_THRESHOLD};
+
+/// CONTEXT is the number of characters the score of a boundary depends on, on each side.
+const CONTEXT: usize = 3;
+
+impl Model {
+    /// parse_stream returns an iterator over splitted strings read from reader.
+    ///
+    /// Text is read line by line, and chunks are yielded as soon as their boundaries are decided.
+    /// The score of a boundary depends on the 3 characters before and after it, so the last 3
+    /// characters of the yielded text are kept as context and a boundary is decided once 3 characters
+    /// after it have been read. The result is the same as parsing the whole text at once.
+    ///
+    /// * `reader` - reader of input sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let model = budoux::models::default_japanese_model();
+    /// let reader = std::io::Cursor::new("これはテストです。\n今日は晴天です。");
+    /// let words = model
+    ///     .parse_stream(reader)
+    ///     .collect::<std::io::Result<Vec<_>>>()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(words, vec!["これは", "テストです。", "\n今日は", "晴天です。"]);
+    /// ```
+    pub fn parse_stream<'a, R: BufRead + 'a>(
+        &'a self,
+        mut reader: R,
+    ) -> impl Iterator<Item = io::Result<String>> + 'a {
+        // buf is the context followed by the text read but not yet moved to chunk, which starts at
+        // decided. Every boundary before decided has been scored.
+        let mut buf = String::new();
+        let mut decided = 0;
+        let mut chunk = String::new();
+        let mut ready = VecDeque::new();
+        let mut eof = false;
+        core::iter::from_fn(move || loop {
+            if let Some(chunk) = ready.pop_front() {
+                return Some(Ok(chunk));
+            }
+            if eof {
+                return None;
+            }
+            match reader.read_line(&mut buf) {
+                Ok(0) => eof = true,
+                Ok(_) => {}
+                Err(e) => {
+                    eof = true;
+                    return Some(Err(e));
+                }
+            }
+
+            let chars = buf.char_indices().collect::<Vec<_>>();
+            // the boundary before the first character of the text is not scored.
+            let first = chars.partition_point(|&(i, _)| i < decided).max(1);
+            let last = if eof {
+                chars.len()
+            } else {
+                chars.len().saturating_sub(CONTEXT - 1)
+            };
+            for i in first..last {
+                if self.score(&buf, &chars, i) >= DEFAULT_THRESHOLD {
+                    chunk.push_str(&buf[decided..chars[i].0]);
+                    ready.push_back(core::mem::take(&mut chunk));
+                    decided = chars[i].0;
+                }
+            }
+            if last > first {
+                let end = chars.get(last).map_or(buf.len(), |&(i, _)| i);
+                chunk.push_str(&buf[decided..end]);
+                decided = end;
+            }
+            if eof {
+                if !chunk.is_empty() {
+                    ready.push_back(core::mem::take(&mut chunk));
+                }
+                continue;
+            }
+
+            let pending = chars.partition_point(|&(i, _)| i < decided);
+            let keep = pending.checked_sub(CONTEXT).map_or(0, |i| chars[i].0);
+            buf.drain(..keep);
+            decided -= keep;
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, BufRead, BufReader, Cursor, Read};
+
+    fn parse_stream(m: &crate::Model, reader: impl BufRead) -> Vec<String> {
+        m.parse_stream(reader).collect::<io::Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn test_parse_stream() {
+        let m = crate::models::default_japanese_model();
+
+        assert_eq!(parse_stream(m, Cursor::new("")), Vec::<String>::new());
+        for input in [
+            "日本語",
+            "水と油\n",
+            "これはテストです。\n今日は晴天です。",
+            "日本語の文章において\n語の区切りに\n\n空白を挟んで\n記述すること\n",
+            "あ\nい\nう\nえ\nお\n",
+        ] {
+            assert_eq!(parse_stream(m, Cursor::new(input)), m.parse(input));
+            assert_eq!(
+                parse_stream(m, BufReader::with_capacity(1, input.as_bytes())),
+                m.parse(input)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_stream_long_chunk() {
+        // ASCII text has no boundary with the japanese model, so every line joins one chunk.
+        let m = crate::models::default_japanese_model();
+        let ascii = "The quick brown fox jumps over the lazy dog.\n".repeat(1000);
+        let mixed = "The quick brown fox\nこれはテストです。\n".repeat(100);
+
+        assert_eq!(parse_stream(m, Cursor::new(&ascii)), vec![ascii.clone()]);
+        for input in [ascii, mixed] {
+            assert_eq!(parse_stream(m, Cursor::new(&input)), m.parse(&input));
+        }
+    }
+
+    #[test]
+    fn test_parse_stream_error() {
+        struct ErrorReader;
+
+        impl Read for ErrorReader {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("error"))
+            }
+        }
+
+        let m = crate::models::default_japanese_model();
+        let mut words = m.parse_stream(BufReader::new(ErrorReader));
+
+        assert!(words.next().unwrap().is_err());
+        assert!(words.next().is_none());
+    }
+}
@@ -0,0 +1,131 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use wasm_bindgen::prelude::*;
+
+use crate::Model;
+
+enum Inner {
+    Static(&'static Model),
+    Owned(Model),
+}
+
+/// WasmModel is JavaScript binding of `Model`.
+#[wasm_bindgen]
+pub struct WasmModel {
+    inner: Inner,
+}
+
+#[wasm_bindgen]
+impl WasmModel {
+    /// new_japanese returns trained machine learning model for japanese.
+    #[wasm_bindgen(js_name = newJapanese)]
+    pub fn new_japanese() -> WasmModel {
+        Self {
+            inner: Inner::Static(crate::models::default_japanese_model()),
+        }
+    }
+
+    /// new_from_json loads model from BudouX model json.
+    #[wasm_bindgen(js_name = newFromJson)]
+    pub fn new_from_json(json: &str) -> Result<WasmModel, JsError> {
+        let model = Model::from_str(json).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(Self {
+            inner: Inner::Owned(model),
+        })
+    }
+
+    /// new_from_bytes loads model from BudouX model json bytes, such as a `Uint8Array` of a fetched
+    /// response body, without decoding it into a JavaScript string first.
+    ///
+    /// ```js
+    /// const response = await fetch("ja.json");
+    /// const bytes = new Uint8Array(await response.arrayBuffer());
+    /// const model = WasmModel.newFromBytes(bytes);
+    /// ```
+    #[wasm_bindgen(js_name = newFromBytes)]
+    pub fn new_from_bytes(bytes: &[u8]) -> Result<WasmModel, JsError> {
+        let model = Model::from_slice(bytes).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(Self {
+            inner: Inner::Owned(model),
+        })
+    }
+
+    /// parse returns splitted strings from input.
+    pub fn parse(&self, input: &str) -> Vec<String> {
+        self.model().parse_iter(input).map(String::from).collect()
+    }
+
+    /// parse_offsets returns offsets where a break is inserted, without copying the chunks.
+    ///
+    /// Offsets are in UTF-16 code units, so that they can be passed to `String.prototype.slice`
+    /// of the input string in JavaScript.
+    #[wasm_bindgen(js_name = parseOffsets)]
+    pub fn parse_offsets(&self, input: &str) -> Vec<u32> {
+        let mut offsets = Vec::new();
+        let mut pos = 0;
+        let mut units = 0;
+        for boundary in self.model().parse_boundaries(input) {
+            units += input[pos..boundary].encode_utf16().count();
+            offsets.push(units as u32);
+            pos = boundary;
+        }
+        offsets
+    }
+}
+
+impl WasmModel {
+    fn model(&self) -> &Model {
+        match &self.inner {
+            Inner::Static(model) => model,
+            Inner::Owned(model) => model,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_parse() {
+        let m = super::WasmModel::new_japanese();
+
+        assert_eq!(m.parse(""), Vec::<String>::new());
+        assert_eq!(
+            m.parse("これはテストです。"),
+            vec!["これは", "テストです。"]
+        );
+    }
+
+    #[test]
+    fn test_new_from_bytes() {
+        let m = super::WasmModel::new_from_bytes(include_bytes!("../resources/ja.json")).unwrap();
+
+        assert_eq!(m.model(), crate::models::default_japanese_model());
+        assert_eq!(
+            m.parse("これはテストです。"),
+            vec!["これは", "テストです。"]
+        );
+    }
+
+    #[test]
+    fn test_parse_offsets() {
+        let m = super::WasmModel::new_japanese();
+
+        assert_eq!(m.parse_offsets(""), Vec::<u32>::new());
+        assert_eq!(m.parse_offsets("これはテストです。"), vec![3]);
+
+        let input = "😀これはテストです。";
+        let utf16 = input.encode_utf16().collect::<Vec<_>>();
+        let chunks = m.parse(input);
+        let offsets = m.parse_offsets(input);
+        assert_eq!(offsets.len() + 1, chunks.len());
+        for (chunk, (start, end)) in chunks.iter().zip(
+            core::iter::once(0)
+                .chain(offsets.iter().map(|&o| o as usize))
+                .zip(offsets.iter().map(|&o| o as usize).chain([utf16.len()])),
+        ) {
+            assert_eq!(&String::from_utf16(&utf16[start..end]).unwrap(), chunk);
+        }
+    }
+}